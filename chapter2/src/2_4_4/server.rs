@@ -1,6 +1,10 @@
-use std::io::{BufRead, BufReader, Write};
+use lib::io::LimitedReader;
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
 use std::net::{TcpListener, TcpStream};
 
+/// Request bodies larger than this are rejected.
+const MAX_BODY_BYTES: u64 = 1024 * 1024;
+
 fn get_operation(stream: &mut TcpStream) -> std::io::Result<()> {
     let body = "HTTP server sample";
     writeln!(stream, "HTTP/1.1 200 OK")?;
@@ -12,12 +16,54 @@ fn get_operation(stream: &mut TcpStream) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Responds with an error `status` such as "400 Bad Request" and no body.
+fn error_response(stream: &mut TcpStream, status: &str) -> std::io::Result<()> {
+    writeln!(stream, "HTTP/1.1 {}", status)?;
+    writeln!(stream, "Content-Length: 0")?;
+    writeln!(stream, "Connection: close")?;
+    writeln!(stream)?;
+    Ok(())
+}
+
+/// Reads header lines up to the empty line and returns the value of Content-Length (0 if absent).
+/// Fails with `ErrorKind::InvalidData` when a line is not UTF-8 or Content-Length is not a number.
+fn read_content_length<R: BufRead>(reader: &mut R) -> std::io::Result<u64> {
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            return Ok(content_length);
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse().map_err(|_| {
+                    std::io::Error::new(ErrorKind::InvalidData, "invalid Content-Length")
+                })?;
+            }
+        }
+    }
+}
+
 fn handle_client(stream: TcpStream) -> std::io::Result<()> {
     let mut reader = BufReader::new(stream);
 
     let mut first_line = String::new();
     reader.read_line(&mut first_line)?;
 
+    let content_length = match read_content_length(&mut reader) {
+        Ok(n) => n,
+        Err(e) if e.kind() == ErrorKind::InvalidData => {
+            return error_response(reader.get_mut(), "400 Bad Request");
+        }
+        Err(e) => return Err(e),
+    };
+    if content_length > MAX_BODY_BYTES {
+        return error_response(reader.get_mut(), "413 Payload Too Large");
+    }
+    // without the limit, read_to_end() would wait for the client to close the connection
+    let mut body = Vec::new();
+    LimitedReader::new(&mut reader, content_length).read_to_end(&mut body)?;
+
     let mut params = first_line.split_whitespace();
     let method = params.next();
     let path = params.next();
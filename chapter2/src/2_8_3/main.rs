@@ -1,15 +1,63 @@
 use flate2::{write::GzEncoder, Compression};
-use lib::io::MultiWriter;
+use lib::io::{LimitedReader, MultiWriter, WritePolicy};
 use std::{
     io::{self, Read, Write},
     net::{TcpListener, TcpStream},
+    thread,
+    time::Duration,
 };
 
+/// Request bodies larger than this are rejected.
+const MAX_BODY_BYTES: u64 = 1024 * 1024;
+
+/// Reads from and writes to a stalled peer fail after this, instead of blocking forever.
+const PEER_TIMEOUT: Duration = Duration::from_secs(10);
+
 fn handle_tcp_request(mut stream: TcpStream) -> io::Result<()> {
-    let req = read_request_line(&mut stream)?;
+    stream.set_read_timeout(Some(PEER_TIMEOUT))?;
+    stream.set_write_timeout(Some(PEER_TIMEOUT))?;
+
+    let headers =
+        read_request_line(&mut stream).and_then(|req| Ok((req, read_content_length(&mut stream)?)));
+    let (req, content_length) = match headers {
+        Ok(headers) => headers,
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+            return error_response(stream, "400 Bad Request");
+        }
+        Err(e) => return Err(e),
+    };
+    if content_length > MAX_BODY_BYTES {
+        return error_response(stream, "413 Payload Too Large");
+    }
+    // discards the body, reading no further than it
+    io::copy(
+        &mut LimitedReader::new(&mut stream, content_length),
+        &mut io::sink(),
+    )?;
+
     handle_http_request(&req, stream)
 }
 
+/// Reads header lines up to the empty line and returns the value of Content-Length (0 if absent).
+/// Fails with `io::ErrorKind::InvalidData` when Content-Length is not a number.
+fn read_content_length(req: &mut TcpStream) -> io::Result<u64> {
+    let mut content_length = 0;
+    loop {
+        let line = read_request_line(req)?;
+        if line.trim().is_empty() {
+            return Ok(content_length);
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse().map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid Content-Length")
+                })?;
+            }
+        }
+    }
+}
+
+/// Fails with `io::ErrorKind::InvalidData` when the line is not UTF-8.
 fn read_request_line(req: &mut TcpStream) -> io::Result<String> {
     let mut line_buf = Vec::<u8>::new();
     loop {
@@ -21,7 +69,7 @@ fn read_request_line(req: &mut TcpStream) -> io::Result<String> {
             line_buf.append(&mut ch_buf);
         }
     }
-    Ok(String::from_utf8(line_buf).expect("invalid bytes as UTF-8"))
+    String::from_utf8(line_buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
 
 fn handle_http_request(req: &str, res: TcpStream) -> io::Result<()> {
@@ -33,6 +81,15 @@ fn handle_http_request(req: &str, res: TcpStream) -> io::Result<()> {
     Ok(())
 }
 
+/// Responds with an error `status` such as "400 Bad Request" and no body.
+fn error_response(mut res: TcpStream, status: &str) -> io::Result<()> {
+    writeln!(res, "HTTP/1.1 {}", status)?;
+    writeln!(res, "Content-Length: 0")?;
+    writeln!(res, "Connection: close")?;
+    writeln!(res)?;
+    Ok(())
+}
+
 fn get_root(mut res: TcpStream) -> io::Result<()> {
     let body = r#"{ "Hello": "World" }"#;
 
@@ -42,12 +99,19 @@ fn get_root(mut res: TcpStream) -> io::Result<()> {
     writeln!(res)?;
 
     let gzip_encoder = GzEncoder::new(res, Compression::default());
-    let mut response_writer =
-        MultiWriter::new(vec![Box::new(io::stdout()), Box::new(gzip_encoder)]);
+    // a stalled or disconnected peer must not hold up logging to stdout
+    let mut response_writer = MultiWriter::threaded(
+        vec![Box::new(io::stdout()), Box::new(gzip_encoder)],
+        WritePolicy::BestEffort,
+    );
 
     write!(response_writer, "{}", body)?;
+    response_writer.flush()?;
 
     writeln!(io::stdout())?; // adds a new line to logs
+    for failure in response_writer.take_failures() {
+        eprintln!("failed to respond: {}", failure);
+    }
 
     Ok(())
 }
@@ -57,7 +121,12 @@ fn main() -> io::Result<()> {
 
     for res_stream in listener.incoming() {
         let stream = res_stream?;
-        handle_tcp_request(stream)?;
+        // a thread per connection, so that a stalled peer holds up only its own response
+        thread::spawn(move || {
+            if let Err(e) = handle_tcp_request(stream) {
+                eprintln!("failed to handle request: {}", e);
+            }
+        });
     }
     Ok(())
 }
@@ -1,6 +1,6 @@
 use std::{
     fs::{File, OpenOptions},
-    io::{self, Read, Seek, Write},
+    io::{self, Read, Seek, SeekFrom, Write},
 };
 
 use lib::{
@@ -24,8 +24,9 @@ fn section_reader<R: Read + Seek>(reader: R) -> io::Result<()> {
     Ok(())
 }
 
-fn limited_reader<R: Read + Seek>(reader: R) -> io::Result<()> {
-    let mut limited_reader = LimitedReader::new(reader, 16)?;
+fn limited_reader<R: Read + Seek>(mut reader: R) -> io::Result<()> {
+    reader.seek(SeekFrom::Start(0))?;
+    let mut limited_reader = LimitedReader::new(reader, 16);
     io::copy(&mut limited_reader, &mut io::stdout())?;
     Ok(())
 }
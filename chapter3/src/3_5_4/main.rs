@@ -1,30 +1,21 @@
 use lib::{
     env::temp_file,
-    image::png::{PngAnalyzer, PngChunk, PngCreator},
+    image::png::{PngChunk, PngChunkType, PngDocument, PngError},
 };
 use std::{
     env,
     fs::File,
-    io::{self, Read, Write},
+    io::{Read, Write},
 };
 
-fn embed_text_chunk<R: Read>(orig_png_reader: R, text: &str) -> Vec<u8> {
-    let mut creator = PngCreator::default();
-    let analyzer = PngAnalyzer::new(orig_png_reader);
-
-    let mut chunks = analyzer.chunks();
-    let ihdr_chunk = chunks.next().expect("first chunk must be IHDR");
-    let text_chunk = PngChunk::new_text_chunk(text.to_string());
-
-    creator.add_chunk(ihdr_chunk);
-    creator.add_chunk(text_chunk);
-    for chunk in chunks {
-        creator.add_chunk(chunk);
-    }
-    creator.finalize()
+fn embed_text_chunk<R: Read>(orig_png_reader: R, text: &str) -> Result<Vec<u8>, PngError> {
+    let mut doc = PngDocument::from_reader(orig_png_reader)?;
+    let text_chunk = PngChunk::new_text_chunk("Comment", text)?;
+    doc.insert_after(&PngChunkType::IHDR, text_chunk)?;
+    Ok(doc.finalize())
 }
 
-fn main() -> io::Result<()> {
+fn main() -> anyhow::Result<()> {
     let orig_png_path: String = {
         let mut args = env::args();
         let _ = args.next();
@@ -33,7 +24,7 @@ fn main() -> io::Result<()> {
     };
 
     let orig_png_file = File::open(orig_png_path)?;
-    let new_png_bin = embed_text_chunk(orig_png_file, "ASCII PROGRAMMING++");
+    let new_png_bin = embed_text_chunk(orig_png_file, "ASCII PROGRAMMING++")?;
 
     let new_png_path = temp_file();
     let mut new_png_file = File::create(&new_png_path)?;
@@ -1,3 +1,4 @@
+use lib::io::{split_on, ScanError, Scanner};
 use std::io::{BufRead, BufReader, Result};
 
 const SOURCE: &str = r#"1行目
@@ -37,9 +38,27 @@ fn without_eof() -> Result<()> {
     Ok(())
 }
 
-fn main() -> Result<()> {
+/// Go の `bufio.Scanner` に相当する `Scanner` を使うパターン。
+/// トークンは内部バッファから借用されるので、1行ごとに `String` を確保しない。
+fn with_scanner() -> std::result::Result<(), ScanError> {
+    let mut scanner = Scanner::new(SOURCE.as_bytes());
+    while let Some(line) = scanner.scan_str()? {
+        println!("{}", line);
+    }
+
+    // 区切り文字は差し替えられる。例えば `find -print0` の出力は NUL 区切り。
+    let mut scanner = Scanner::with_split(&b"./a.txt\0./b c.txt\0"[..], split_on(b'\0'));
+    while let Some(path) = scanner.scan_str()? {
+        println!("{}", path);
+    }
+
+    Ok(())
+}
+
+fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     with_eof()?;
     without_eof()?;
+    with_scanner()?;
 
     Ok(())
 }
@@ -1,26 +1,40 @@
-//! Rust には scanf 関数はない。したがって、この節は本来は実装できない。
-//! Rust のマクロを使用すると scanf に近い実装を作ることができるので、参考程度に実装しておく。
-//! マクロを自前実装する以外の選択肢としては、下記のようなクレートを使用する手もある。
+//! Rust には scanf 関数はない。
+//! そこで、Go の fmt.Fscan に相当するものとして `lib::fmt::scan` を用意した。
+//! `"%d %f %s"` や `{}` を使った書式で、任意の `BufRead` から直接読み込むことができる。
+//! 自前実装する以外の選択肢としては、下記のようなクレートを使用する手もある。
 //! - [text_io](https://github.com/oli-obk/rust-si): scan マクロが存在する。
 //! - [scan_fmt](https://github.com/wlentz/scan_fmt): 同様に scan_fmt マクロが存在する。
 
-/// scan! マクロ。
-/// scan!("<入れた文字>", セパレータの設定, 型情報*) と値を入れていくと、scanf とほぼ同等の動きをする。
-///
-/// Example:
-/// ```rust
-/// scan!("123 text", char::is_whitespacce, i32, String);
-/// ```
-macro_rules! scan {
-    ( $string:expr, $sep:expr, $( $x:ty ),+ ) => {{
-        let mut iter = $string.split($sep);
-        ($(iter.next().map(|word| word.parse::<$x>().ok().expect(&format!("couldn't parse {}", word))).unwrap(),)*)
-    }}
-}
+use lib::fmt::{scan, ScanfError};
+use std::io::BufReader;
 
 const SOURCE: &str = "123 1.234 1.0e4 test";
 
-fn main() {
-    let (i, f, g, s) = scan!(SOURCE, char::is_whitespace, i32, f64, f64, String);
+const LOG: &str = r#"2021-06-01 x=12,y=-3
+2021-06-02 x=5,y=abc
+"#;
+
+fn main() -> Result<(), ScanfError> {
+    // 空白区切りの値を型に合わせて読み込む。
+    let (i, f, g, s): (i32, f64, f64, String) = scan(&mut SOURCE.as_bytes(), "%d %f %f %s")?;
     println!("i={} f={} g={} s={}", i, f, g, s);
+
+    // 書式中の文字はそのまま一致する必要がある。`%2d` のように幅も指定できる。
+    // 同じリーダーから続けて読み込むと、前回の続きから読み込まれる。
+    let mut reader = BufReader::new(LOG.as_bytes());
+    loop {
+        let result: Result<(u16, u8, u8, i32, i32), _> = scan(&mut reader, "%4d-%2d-%2d x=%d,y=%d");
+        match result {
+            Ok((year, month, day, x, y)) => {
+                println!("{}/{}/{}: x={} y={}", year, month, day, x, y)
+            }
+            // パースに失敗してもパニックせず、何番目のフィールドがどこで失敗したかがわかる。
+            Err(e) => {
+                println!("error: {}", e);
+                break;
+            }
+        }
+    }
+
+    Ok(())
 }
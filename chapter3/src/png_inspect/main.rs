@@ -0,0 +1,408 @@
+//! Inspects PNG files.
+//!
+//! Exits with 1 when the file is not a valid PNG (or the requested chunk is not found),
+//! and with 2 on wrong usage.
+
+use anyhow::{anyhow, bail, Context};
+use lib::image::png::{
+    Ihdr, Itxt, PngAnalyzer, PngChunk, PngChunkType, PngDocument, PngError, Text, Ztxt,
+};
+use serde_json::{json, Value};
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader, Write},
+    process,
+};
+
+const USAGE: &str = "usage: png-inspect [--json] <command> <file> [<args>]
+
+commands:
+    list                list chunks with offsets, lengths, CRCs and properties
+    info                dump IHDR and textual metadata
+    validate            check CRCs and chunk ordering
+    extract <type> [n]  write raw data of the n-th (default 0) <type> chunk to stdout
+    hexdump <type> [n]  hex-dump data of the n-th (default 0) <type> chunk";
+
+enum Command {
+    List,
+    Info,
+    Validate,
+    Extract(PngChunkType, usize),
+    Hexdump(PngChunkType, usize),
+}
+
+struct Args {
+    json: bool,
+    command: Command,
+    path: String,
+}
+
+/// Chunk with its position in the file.
+struct Located {
+    offset: u64,
+    chunk: PngChunk,
+}
+
+/// Chunks read until the end of file or the first structural error.
+struct Loaded {
+    chunks: Vec<Located>,
+    error: Option<PngError>,
+}
+
+impl Loaded {
+    fn find(&self, typ: &PngChunkType, n: usize) -> anyhow::Result<&Located> {
+        self.chunks
+            .iter()
+            .filter(|c| c.chunk.typ() == typ)
+            .nth(n)
+            .ok_or_else(|| anyhow!("{} chunk #{} not found", typ, n))
+    }
+}
+
+fn main() {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("png-inspect: {}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+    match run(&args) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("png-inspect: {:#}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_args<I>(args: I) -> anyhow::Result<Args>
+where
+    I: Iterator<Item = String>,
+{
+    let mut json = false;
+    let mut positional = Vec::<String>::new();
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            _ if arg.starts_with('-') => bail!("unknown option: {}", arg),
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let command = positional.next().context("missing command")?;
+    let path = positional.next().context("missing file")?;
+    let mut chunk_arg = || -> anyhow::Result<(PngChunkType, usize)> {
+        let typ = positional
+            .next()
+            .context("missing chunk type")?
+            .parse::<PngChunkType>()?;
+        let n = match positional.next() {
+            Some(n) => n.parse().context("chunk index must be a number")?,
+            None => 0,
+        };
+        Ok((typ, n))
+    };
+    let command = match command.as_str() {
+        "list" => Command::List,
+        "info" => Command::Info,
+        "validate" => Command::Validate,
+        "extract" if json => bail!("extract writes raw bytes; --json is not supported"),
+        "extract" => {
+            let (typ, n) = chunk_arg()?;
+            Command::Extract(typ, n)
+        }
+        "hexdump" => {
+            let (typ, n) = chunk_arg()?;
+            Command::Hexdump(typ, n)
+        }
+        _ => bail!("unknown command: {}", command),
+    };
+    if let Some(extra) = positional.next() {
+        bail!("unexpected argument: {}", extra);
+    }
+
+    Ok(Args {
+        json,
+        command,
+        path,
+    })
+}
+
+/// # Returns
+///
+/// Whether the file is valid.
+fn run(args: &Args) -> anyhow::Result<bool> {
+    let file = File::open(&args.path).with_context(|| format!("cannot open {}", args.path))?;
+    let loaded = load(BufReader::new(file));
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let valid = match &args.command {
+        Command::List => list(&mut out, &loaded, args.json)?,
+        Command::Info => info(&mut out, &loaded, args.json)?,
+        Command::Validate => validate(&mut out, loaded, args.json)?,
+        Command::Extract(typ, n) => {
+            out.write_all(loaded.find(typ, *n)?.chunk.data())?;
+            true
+        }
+        Command::Hexdump(typ, n) => hexdump(&mut out, loaded.find(typ, *n)?, args.json)?,
+    };
+    out.flush()?;
+    Ok(valid)
+}
+
+/// Reads chunks without verifying CRCs so that all of them can be reported.
+fn load<R>(reader: R) -> Loaded
+where
+    R: io::Read,
+{
+    let mut loaded = Loaded {
+        chunks: vec![],
+        error: None,
+    };
+    let chunks = match PngAnalyzer::new(reader).try_chunks() {
+        Ok(chunks) => chunks,
+        Err(e) => {
+            loaded.error = Some(e);
+            return loaded;
+        }
+    };
+
+    // Following the 8-byte signature.
+    let mut offset = 8u64;
+    for chunk in chunks {
+        match chunk {
+            Ok(chunk) => {
+                let len = chunk.data().len() as u64;
+                loaded.chunks.push(Located { offset, chunk });
+                // length, type and CRC fields
+                offset += 12 + len;
+            }
+            Err(e) => {
+                loaded.error = Some(e);
+                break;
+            }
+        }
+    }
+    loaded
+}
+
+fn list<W>(out: &mut W, loaded: &Loaded, json: bool) -> anyhow::Result<bool>
+where
+    W: Write,
+{
+    if json {
+        let chunks: Vec<Value> = loaded
+            .chunks
+            .iter()
+            .map(|Located { offset, chunk }| {
+                let typ = chunk.typ();
+                json!({
+                    "offset": offset,
+                    "type": typ.to_string(),
+                    "length": chunk.data().len(),
+                    "crc": chunk.crc(),
+                    "crc_ok": chunk.verify_crc().is_ok(),
+                    "ancillary": typ.is_ancillary(),
+                    "private": typ.is_private(),
+                    "safe_to_copy": typ.is_safe_to_copy(),
+                })
+            })
+            .collect();
+        let error = loaded.error.as_ref().map(ToString::to_string);
+        writeln!(out, "{:#}", json!({ "chunks": chunks, "error": error }))?;
+    } else {
+        writeln!(out, "{:>10}  TYPE  {:>10}  CRC", "OFFSET", "LENGTH")?;
+        for Located { offset, chunk } in &loaded.chunks {
+            let typ = chunk.typ();
+            let mut flags = vec![if typ.is_ancillary() {
+                "ancillary"
+            } else {
+                "critical"
+            }];
+            if typ.is_private() {
+                flags.push("private");
+            }
+            if typ.is_safe_to_copy() {
+                flags.push("safe-to-copy");
+            }
+            if chunk.verify_crc().is_err() {
+                flags.push("BAD-CRC");
+            }
+            writeln!(
+                out,
+                "{:>10}  {}  {:>10}  {:08X}  {}",
+                offset,
+                typ,
+                chunk.data().len(),
+                chunk.crc(),
+                flags.join(" ")
+            )?;
+        }
+        if let Some(e) = &loaded.error {
+            writeln!(out, "error: {}", e)?;
+        }
+    }
+    let crcs_ok = loaded.chunks.iter().all(|c| c.chunk.verify_crc().is_ok());
+    Ok(loaded.error.is_none() && crcs_ok)
+}
+
+fn info<W>(out: &mut W, loaded: &Loaded, json: bool) -> anyhow::Result<bool>
+where
+    W: Write,
+{
+    let ihdr = match loaded.chunks.first() {
+        Some(Located { chunk, .. }) if chunk.typ() == &PngChunkType::IHDR => {
+            Ihdr::from_chunk(chunk)?
+        }
+        _ => match &loaded.error {
+            Some(e) => bail!("{}", e),
+            None => bail!(PngError::MissingChunk(PngChunkType::IHDR)),
+        },
+    };
+
+    // (chunk type, offset, keyword, language tag and text or the error decoding them)
+    type Decoded = Result<(String, Option<String>, String), PngError>;
+    let mut texts = Vec::<(String, u64, Decoded)>::new();
+    for Located { offset, chunk } in &loaded.chunks {
+        let typ = chunk.typ();
+        let text = if typ == &PngChunkType::TEXT {
+            Text::from_chunk(chunk).map(|t| (t.keyword, None, t.text))
+        } else if typ == &PngChunkType::ZTXT {
+            Ztxt::from_chunk(chunk).map(|t| (t.keyword, None, t.text))
+        } else if typ == &PngChunkType::ITXT {
+            Itxt::from_chunk(chunk).map(|t| (t.keyword, Some(t.language_tag), t.text))
+        } else {
+            continue;
+        };
+        // a broken chunk is reported in place, not to hide the rest
+        texts.push((typ.to_string(), *offset, text));
+    }
+    let texts_ok = texts.iter().all(|(_, _, text)| text.is_ok());
+
+    if json {
+        let texts: Vec<Value> = texts
+            .into_iter()
+            .map(|(typ, offset, text)| match text {
+                Ok((keyword, language_tag, text)) => json!({
+                    "type": typ,
+                    "keyword": keyword,
+                    "language_tag": language_tag,
+                    "text": text,
+                }),
+                Err(e) => json!({
+                    "type": typ,
+                    "offset": offset,
+                    "error": e.to_string(),
+                }),
+            })
+            .collect();
+        let value = json!({
+            "ihdr": {
+                "width": ihdr.width,
+                "height": ihdr.height,
+                "bit_depth": ihdr.bit_depth,
+                "color_type": format!("{:?}", ihdr.color_type),
+                "interlace_method": format!("{:?}", ihdr.interlace_method),
+            },
+            "texts": texts,
+        });
+        writeln!(out, "{:#}", value)?;
+    } else {
+        writeln!(out, "width: {}", ihdr.width)?;
+        writeln!(out, "height: {}", ihdr.height)?;
+        writeln!(out, "bit depth: {}", ihdr.bit_depth)?;
+        writeln!(out, "color type: {:?}", ihdr.color_type)?;
+        writeln!(out, "interlace method: {:?}", ihdr.interlace_method)?;
+        for (typ, offset, text) in texts {
+            match text {
+                Ok((keyword, Some(tag), text)) if !tag.is_empty() => {
+                    writeln!(out, r#"{} {} [{}]: "{}""#, typ, keyword, tag, text)?
+                }
+                Ok((keyword, _, text)) => writeln!(out, r#"{} {}: "{}""#, typ, keyword, text)?,
+                Err(e) => writeln!(out, "{} at offset {}: error: {}", typ, offset, e)?,
+            }
+        }
+    }
+    Ok(loaded.error.is_none() && texts_ok)
+}
+
+fn validate<W>(out: &mut W, loaded: Loaded, json: bool) -> anyhow::Result<bool>
+where
+    W: Write,
+{
+    let mut errors = Vec::<String>::new();
+    for (i, Located { offset, chunk }) in loaded.chunks.iter().enumerate() {
+        if let Err(e) = chunk.verify_crc() {
+            errors.push(format!(
+                "chunk #{} ({}) at offset {}: {}",
+                i,
+                chunk.typ(),
+                offset,
+                e
+            ));
+        }
+    }
+    match loaded.error {
+        Some(e) => errors.push(e.to_string()),
+        None => {
+            let chunks = loaded.chunks.into_iter().map(|c| c.chunk).collect();
+            if let Err(e) = PngDocument::from_chunks(chunks) {
+                errors.push(e.to_string());
+            }
+        }
+    }
+
+    let valid = errors.is_empty();
+    if json {
+        writeln!(out, "{:#}", json!({ "valid": valid, "errors": errors }))?;
+    } else if valid {
+        writeln!(out, "OK")?;
+    } else {
+        for e in errors {
+            writeln!(out, "error: {}", e)?;
+        }
+    }
+    Ok(valid)
+}
+
+fn hexdump<W>(out: &mut W, located: &Located, json: bool) -> anyhow::Result<bool>
+where
+    W: Write,
+{
+    let data = located.chunk.data();
+    if json {
+        let hex: String = data.iter().map(|b| format!("{:02x}", b)).collect();
+        let value = json!({
+            "offset": located.offset,
+            "type": located.chunk.typ().to_string(),
+            "length": data.len(),
+            "data": hex,
+        });
+        writeln!(out, "{:#}", value)?;
+    } else {
+        for (row, bytes) in data.chunks(16).enumerate() {
+            let hex: Vec<String> = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+            let ascii: String = bytes
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            writeln!(out, "{:08x}  {:<47}  |{}|", row * 16, hex.join(" "), ascii)?;
+        }
+    }
+    Ok(true)
+}
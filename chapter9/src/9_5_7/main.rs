@@ -1,7 +1,16 @@
-use lib::path::PathError;
-use std::{collections::HashSet, env};
+use lib::{
+    image::{self, ImageFormat},
+    path::PathError,
+};
+use std::{collections::HashSet, env, fs::File, path::Path};
 use walkdir::WalkDir;
 
+/// マジックバイトから画像フォーマットを判定する
+fn detect_format(path: &Path) -> Option<ImageFormat> {
+    let file = File::open(path).ok()?;
+    image::detect(file).ok().flatten()
+}
+
 fn main() -> Result<(), PathError> {
     let args: Vec<String> = env::args().collect();
     if args.len() == 1 {
@@ -15,28 +24,43 @@ Usage:
         std::process::exit(0);
     }
     let root = &args[1];
-    let image_suffix: HashSet<&'static str> = ["jpg", "jpeg", "png", "webp", "gif", "tiff", "eps"]
-        .iter()
-        .cloned()
-        .collect();
+    // マジックバイトで判定できないフォーマットは拡張子で判断する
+    let image_suffix: HashSet<&'static str> = ["tiff", "eps"].iter().cloned().collect();
+    let detectable_suffix: HashSet<&'static str> = [
+        ImageFormat::Png,
+        ImageFormat::Gif,
+        ImageFormat::Jpeg,
+        ImageFormat::WebP,
+    ]
+    .iter()
+    .flat_map(|format| format.extensions().iter().cloned())
+    .collect();
     // ディレクトリのトラバースのためにwalkdir crateを利用する
-    // ディレクトリ、拡張子が合わないものについては、フィルタして除外する
+    // ディレクトリについては、フィルタして除外する
     // walkdir crateでは指定のディレクトリをスキップするような機能は存在しない
     for path in WalkDir::new(root)
         .into_iter()
         .filter_map(Result::ok)
         .filter(|f| !f.file_type().is_dir())
-        .filter(|f| {
-            let extention_opt = f
-                .path()
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .map(|ext| ext.to_lowercase());
-            let extention = extention_opt.as_deref().unwrap_or("");
-            image_suffix.contains(extention)
-        })
+        .map(|f| f.into_path())
     {
-        println!("{}", path.into_path().display());
+        let extention = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .unwrap_or_default();
+        // 拡張子と中身が一致しないファイルも報告する
+        match detect_format(&path) {
+            Some(format) if format.extensions().contains(&extention.as_str()) => {
+                println!("{}", path.display())
+            }
+            Some(format) => println!("{} (mislabelled: {:?})", path.display(), format),
+            None if detectable_suffix.contains(extention.as_str()) => {
+                println!("{} (mislabelled: not an image)", path.display())
+            }
+            None if image_suffix.contains(extention.as_str()) => println!("{}", path.display()),
+            None => {}
+        }
     }
 
     Ok(())
@@ -0,0 +1,57 @@
+//! Compares `binary::read` with the former implementation,
+//! which allocated a `Vec` per call and reversed it for little endian.
+//!
+//! Run with `cargo bench -p lib --bench binary_read`.
+
+use lib::binary::{self, Endian, FixedLenBytes};
+use std::{
+    hint::black_box,
+    io::{self, Read},
+    time::{Duration, Instant},
+};
+
+const ITERATIONS: usize = 1_000_000;
+
+fn read_with_vec<R, B>(reader: &mut R, endian: &Endian) -> io::Result<B>
+where
+    R: Read,
+    B: FixedLenBytes,
+{
+    let mut buf = vec![0u8; B::len()];
+    reader.read_exact(&mut buf)?;
+    if endian.is_little() {
+        buf.reverse();
+    }
+    Ok(B::new(&buf).expect("buf has wrong length"))
+}
+
+/// Reads `ITERATIONS` u32 values (like fields of packet headers) with `f`.
+fn measure<F>(input: &[u8], endian: &Endian, f: F) -> Duration
+where
+    F: Fn(&mut &[u8], &Endian) -> io::Result<u32>,
+{
+    let start = Instant::now();
+    let mut reader = input;
+    let mut sum = 0u32;
+    for _ in 0..ITERATIONS {
+        sum = sum.wrapping_add(f(&mut reader, endian).unwrap());
+    }
+    black_box(sum);
+    start.elapsed()
+}
+
+fn main() {
+    let input: Vec<u8> = (0..ITERATIONS * 4).map(|i| i as u8).collect();
+
+    for endian in &[Endian::BigEndian, Endian::LittleEndian] {
+        let vec_time = measure(&input, endian, |r, e| read_with_vec(black_box(r), e));
+        let stack_time = measure(&input, endian, |r, e| binary::read(black_box(r), e));
+        println!(
+            "{:?}: Vec per call {:?}, stack buffer {:?} ({:.1}x faster)",
+            endian,
+            vec_time,
+            stack_time,
+            vec_time.as_secs_f64() / stack_time.as_secs_f64()
+        );
+    }
+}
@@ -1,10 +1,31 @@
+mod binary_read;
+mod binary_write;
+mod bit_order;
+mod bit_reader;
+mod bit_writer;
 mod endian;
 mod fixed_len_bytes;
+mod to_fixed_len_bytes;
+mod varint;
+mod varint_error;
 
+pub use binary_read::BinaryRead;
+pub use binary_write::BinaryWrite;
+pub use bit_order::BitOrder;
+pub use bit_reader::BitReader;
+pub use bit_writer::BitWriter;
 pub use endian::Endian;
 pub use fixed_len_bytes::FixedLenBytes;
+pub use to_fixed_len_bytes::ToFixedLenBytes;
+pub use varint::{
+    read_offset_varint, read_sleb128, read_uleb128, read_varint, read_zigzag, write_offset_varint,
+    write_sleb128, write_uleb128, write_varint, write_zigzag, zigzag_decode, zigzag_encode,
+};
+pub use varint_error::VarintError;
+// Derive macros of the same names.
+pub use lib_derive::{BinaryRead, BinaryWrite};
 
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 
 /// Read bytes of fixed size (`B::len()`) from `reader` in `endian` order.
 ///
@@ -24,6 +45,18 @@ use std::io::{self, Read};
 ///     let n_little: u16 = binary::read(&mut bytes, &binary::Endian::LittleEndian)?;
 ///     assert_eq!(n_little, 0x0201);
 ///
+///     // signed integers, floats and arrays (whose elements keep their order)
+///     let mut bytes: &[u8] = &[0xFE, 0xFF, 0x00, 0x00, 0x80, 0x3F, 0x01, 0x00, 0x02, 0x00];
+///     let i: i16 = binary::read(&mut bytes, &binary::Endian::LittleEndian)?;
+///     let f: f32 = binary::read(&mut bytes, &binary::Endian::LittleEndian)?;
+///     let arr: [u16; 2] = binary::read(&mut bytes, &binary::Endian::LittleEndian)?;
+///     assert_eq!((i, f, arr), (-2, 1.0, [1, 2]));
+///
+///     // byte order of the running platform
+///     let mut bytes: &[u8] = &0x01020304u32.to_ne_bytes();
+///     let n_native: u32 = binary::read(&mut bytes, &binary::Endian::Native)?;
+///     assert_eq!(n_native, 0x01020304);
+///
 ///     Ok(())
 /// }
 /// ```
@@ -32,18 +65,143 @@ where
     R: Read,
     B: FixedLenBytes,
 {
-    let mut buf = Vec::<u8>::new();
-    buf.resize(B::len(), 0);
+    let mut stack_buf = [0u8; STACK_BUF_LEN];
+    let mut heap_buf = Vec::<u8>::new();
+    let buf = buf_of_len(&mut stack_buf, &mut heap_buf, B::len());
 
-    reader.read_exact(&mut buf)?;
+    reader.read_exact(buf)?;
 
-    match endian {
-        Endian::BigEndian => {}
-        Endian::LittleEndian => {
-            buf.reverse();
-        }
+    let bytes = if endian.is_little() {
+        B::new_le(buf)
+    } else {
+        B::new(buf)
     }
-
-    let bytes = B::new(&buf).expect("buf has wrong length");
+    .expect("buf has wrong length");
     Ok(bytes)
 }
+
+/// Read values to fill `values`, each in `endian` order.
+///
+/// # Examples
+///
+/// ```
+/// use lib::binary::{self, Endian};
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut bytes: &[u8] = &[0x01, 0x00, 0x02, 0x00, 0x03, 0x00];
+///     let mut values = [0u16; 3];
+///     binary::read_into(&mut bytes, &mut values, &Endian::LittleEndian)?;
+///     assert_eq!(values, [1, 2, 3]);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn read_into<R, B>(reader: &mut R, values: &mut [B], endian: &Endian) -> io::Result<()>
+where
+    R: Read,
+    B: FixedLenBytes,
+{
+    for value in values {
+        *value = read(reader, endian)?;
+    }
+    Ok(())
+}
+
+/// Write `value` as bytes of fixed size (`B::len()`) to `writer` in `endian` order.
+///
+/// # Examples
+///
+/// ```
+/// use lib::binary::{self, Endian};
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut bytes = Vec::<u8>::new();
+///     binary::write(&mut bytes, 0x01020304u32, &Endian::BigEndian)?;
+///     binary::write(&mut bytes, 0x0102u16, &Endian::LittleEndian)?;
+///     assert_eq!(bytes, [0x01, 0x02, 0x03, 0x04, 0x02, 0x01]);
+///
+///     // round trip with `binary::read()`
+///     let n: u32 = binary::read(&mut bytes.as_slice(), &Endian::BigEndian)?;
+///     assert_eq!(n, 0x01020304);
+///
+///     let mut bytes = Vec::<u8>::new();
+///     binary::write(&mut bytes, [1i16, -1], &Endian::LittleEndian)?;
+///     binary::write(&mut bytes, -0.5f64, &Endian::BigEndian)?;
+///     assert_eq!(bytes[..4], [0x01, 0x00, 0xFF, 0xFF]);
+///     assert_eq!(bytes[4..], (-0.5f64).to_be_bytes());
+///
+///     Ok(())
+/// }
+/// ```
+pub fn write<W, B>(writer: &mut W, value: B, endian: &Endian) -> io::Result<()>
+where
+    W: Write,
+    B: ToFixedLenBytes,
+{
+    write_ref(writer, &value, endian)
+}
+
+/// Write all of `values`, each in `endian` order.
+///
+/// # Examples
+///
+/// ```
+/// use lib::binary::{self, Endian};
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut bytes = Vec::<u8>::new();
+///     binary::write_from(&mut bytes, &[1u16, 2, 3], &Endian::BigEndian)?;
+///     assert_eq!(bytes, [0x00, 0x01, 0x00, 0x02, 0x00, 0x03]);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn write_from<W, B>(writer: &mut W, values: &[B], endian: &Endian) -> io::Result<()>
+where
+    W: Write,
+    B: ToFixedLenBytes,
+{
+    for value in values {
+        write_ref(writer, value, endian)?;
+    }
+    Ok(())
+}
+
+fn write_ref<W, B>(writer: &mut W, value: &B, endian: &Endian) -> io::Result<()>
+where
+    W: Write,
+    B: ToFixedLenBytes,
+{
+    let mut stack_buf = [0u8; STACK_BUF_LEN];
+    let mut heap_buf = Vec::<u8>::new();
+    let buf = buf_of_len(&mut stack_buf, &mut heap_buf, B::len());
+
+    if endian.is_little() {
+        value.to_bytes_le(buf)
+    } else {
+        value.to_bytes(buf)
+    }
+    .expect("buf has wrong length");
+
+    writer.write_all(buf)
+}
+
+/// Values up to this length (that of `u128`) are read and written without allocation.
+const STACK_BUF_LEN: usize = 16;
+
+/// Slice of `len` bytes, taken from `stack_buf` if long enough or else from `heap_buf`.
+fn buf_of_len<'b>(
+    stack_buf: &'b mut [u8; STACK_BUF_LEN],
+    heap_buf: &'b mut Vec<u8>,
+    len: usize,
+) -> &'b mut [u8] {
+    if len <= STACK_BUF_LEN {
+        &mut stack_buf[..len]
+    } else {
+        heap_buf.resize(len, 0);
+        heap_buf
+    }
+}
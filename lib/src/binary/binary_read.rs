@@ -0,0 +1,72 @@
+use std::io::{self, Read};
+
+/// Represents types decoded from a byte stream field by field.
+///
+/// Usually implemented with `#[derive(BinaryRead)]`, which reads fields in declaration order
+/// by `binary::read()` (so their types must implement `FixedLenBytes`).
+/// Layout is controlled by `#[binary(...)]` attributes:
+///
+/// - `endian = "big" | "little" | "native"` on the struct or a field (default: big).
+/// - `len_prefix = "u16"` on a `Vec` field: element count of the given type precedes the elements.
+/// - `magic = b"..."` or `magic = 42`: fails unless the field has the value.
+/// - `pad_before = N`, `pad_after = N`: bytes to skip.
+/// - `nested`: the field type implements `BinaryRead` itself.
+/// - `crate = "my_lib"` on the struct: path to this crate when the dependency is renamed.
+///
+/// # Examples
+///
+/// ```
+/// use lib::binary::{BinaryRead, BinaryWrite};
+/// use std::io;
+///
+/// /// BMP file header
+/// #[derive(BinaryRead, BinaryWrite, Debug, PartialEq)]
+/// #[binary(endian = "little")]
+/// struct FileHeader {
+///     #[binary(magic = b"BM")]
+///     signature: [u8; 2],
+///     #[binary(pad_after = 4)]
+///     file_size: u32,
+///     pixel_offset: u32,
+/// }
+///
+/// #[derive(BinaryRead, BinaryWrite, Debug, PartialEq)]
+/// struct Record {
+///     #[binary(nested)]
+///     header: FileHeader,
+///     #[binary(len_prefix = "u8")]
+///     values: Vec<u16>,
+///     #[binary(endian = "little")]
+///     checksum: u16,
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let bytes = [
+///         b'B', b'M', 0x3A, 0, 0, 0, 0, 0, 0, 0, 0x36, 0, 0, 0, // FileHeader
+///         2, 0x01, 0x02, 0x03, 0x04, // values
+///         0xCD, 0xAB, // checksum
+///     ];
+///     let record = Record::read_from(&mut &bytes[..])?;
+///     assert_eq!(record.header.file_size, 0x3A);
+///     assert_eq!(record.header.pixel_offset, 0x36);
+///     assert_eq!(record.values, vec![0x0102, 0x0304]);
+///     assert_eq!(record.checksum, 0xABCD);
+///
+///     let mut written = vec![];
+///     record.write_to(&mut written)?;
+///     assert_eq!(written, bytes);
+///
+///     let err = FileHeader::read_from(&mut &b"GIF89a\0\0\0\0\0\0\0\0"[..]).unwrap_err();
+///     assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+///     Ok(())
+/// }
+/// ```
+pub trait BinaryRead: Sized {
+    /// # Failures
+    ///
+    /// - `io::ErrorKind::UnexpectedEof` when `reader` ends in the middle.
+    /// - `io::ErrorKind::InvalidData` when a magic number does not match.
+    fn read_from<R>(reader: &mut R) -> io::Result<Self>
+    where
+        R: Read;
+}
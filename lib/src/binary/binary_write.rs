@@ -0,0 +1,33 @@
+use std::io::{self, Write};
+
+/// Represents types encoded into a byte stream field by field.
+///
+/// Usually implemented with `#[derive(BinaryWrite)]`, which accepts the same attributes as
+/// `#[derive(BinaryRead)]` and writes what it reads.
+/// Paddings are filled with zeros.
+///
+/// # Examples
+///
+/// ```
+/// use lib::binary::BinaryWrite;
+/// use std::io;
+///
+/// #[derive(BinaryWrite)]
+/// struct Point(i16, #[binary(endian = "little")] i16);
+///
+/// fn main() -> io::Result<()> {
+///     let mut written = vec![];
+///     Point(-2, 1).write_to(&mut written)?;
+///     assert_eq!(written, [0xFF, 0xFE, 0x01, 0x00]);
+///     Ok(())
+/// }
+/// ```
+pub trait BinaryWrite {
+    /// # Failures
+    ///
+    /// - Errors from `writer`.
+    /// - `io::ErrorKind::InvalidInput` when a `Vec` has more elements than its length prefix can represent.
+    fn write_to<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write;
+}
@@ -0,0 +1,8 @@
+/// Order in which bits are taken out of (or put into) each byte.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum BitOrder {
+    /// From the most significant bit: network headers, PNG scanlines of low bit depth.
+    MsbFirst,
+    /// From the least significant bit: Deflate, GIF's LZW codes.
+    LsbFirst,
+}
@@ -0,0 +1,146 @@
+use std::io::{self, Read};
+
+use super::BitOrder;
+
+/// Reads values of arbitrary bit width from given reader.
+///
+/// With `BitOrder::MsbFirst`, the first bit of a value is the most significant one;
+/// with `BitOrder::LsbFirst`, values are packed from the least significant bit of each byte
+/// and the first bit is the least significant one.
+///
+/// # Examples
+///
+/// ```
+/// use lib::binary::{BitOrder, BitReader};
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     // IPv4 header starts with version (4 bits) and IHL (4 bits)
+///     let mut reader = BitReader::new(&[0x45, 0b1010_0000][..], BitOrder::MsbFirst);
+///     assert_eq!(reader.read_bits(4)?, 4);
+///     assert_eq!(reader.read_bits(4)?, 5);
+///     assert_eq!(reader.peek_bits(3)?, 0b101);
+///     assert!(reader.read_bool()?);
+///     reader.align();
+///     assert_eq!(
+///         reader.read_bool().unwrap_err().kind(),
+///         io::ErrorKind::UnexpectedEof
+///     );
+///
+///     // Deflate block header: BFINAL (1 bit) and BTYPE (2 bits) from the lowest bit
+///     let mut reader = BitReader::new(&[0b0000_0011][..], BitOrder::LsbFirst);
+///     assert!(reader.read_bool()?);
+///     assert_eq!(reader.read_bits(2)?, 1);
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BitReader<R>
+where
+    R: Read,
+{
+    reader: R,
+    order: BitOrder,
+    /// Bits read from `reader` but not consumed yet: the lowest `len` bits.
+    /// The next bit is the highest of them with `MsbFirst`, and the lowest with `LsbFirst`.
+    buf: u128,
+    len: u32,
+}
+
+impl<R> BitReader<R>
+where
+    R: Read,
+{
+    pub fn new(reader: R, order: BitOrder) -> Self {
+        Self {
+            reader,
+            order,
+            buf: 0,
+            len: 0,
+        }
+    }
+
+    /// Read `n` bits as an unsigned integer.
+    ///
+    /// # Failures
+    ///
+    /// - `io::ErrorKind::UnexpectedEof` when `reader` has less than `n` bits left.
+    ///   Bits already read stay available.
+    /// - Errors from `reader`.
+    ///
+    /// # Panics
+    ///
+    /// When `n > 64`.
+    pub fn read_bits(&mut self, n: u32) -> io::Result<u64> {
+        let value = self.peek_bits(n)?;
+        self.consume(n);
+        Ok(value)
+    }
+
+    /// Read 1 bit.
+    ///
+    /// # Failures
+    ///
+    /// Same as `read_bits()`.
+    pub fn read_bool(&mut self) -> io::Result<bool> {
+        Ok(self.read_bits(1)? == 1)
+    }
+
+    /// Read `n` bits without consuming them.
+    ///
+    /// # Failures
+    ///
+    /// Same as `read_bits()`.
+    ///
+    /// # Panics
+    ///
+    /// When `n > 64`.
+    pub fn peek_bits(&mut self, n: u32) -> io::Result<u64> {
+        assert!(n <= 64, "cannot read more than 64 bits at once: {}", n);
+        while self.len < n {
+            let mut byte = [0u8; 1];
+            self.reader.read_exact(&mut byte)?;
+            self.buf = match self.order {
+                BitOrder::MsbFirst => (self.buf << 8) | u128::from(byte[0]),
+                BitOrder::LsbFirst => self.buf | (u128::from(byte[0]) << self.len),
+            };
+            self.len += 8;
+        }
+        let bits = match self.order {
+            BitOrder::MsbFirst => self.buf >> (self.len - n),
+            BitOrder::LsbFirst => self.buf,
+        };
+        Ok((bits & mask(n)) as u64)
+    }
+
+    /// Skip the rest of the current byte, so that the next read starts at a byte boundary.
+    pub fn align(&mut self) {
+        self.consume(self.len % 8);
+    }
+
+    /// Whether the next read starts at a byte boundary.
+    pub fn is_aligned(&self) -> bool {
+        self.len.is_multiple_of(8)
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Whole bytes buffered by `peek_bits()` are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn consume(&mut self, n: u32) {
+        self.len -= n;
+        match self.order {
+            BitOrder::MsbFirst => self.buf &= mask(self.len),
+            BitOrder::LsbFirst => self.buf >>= n,
+        }
+    }
+}
+
+/// Lowest `n` bits set.
+pub(super) fn mask(n: u32) -> u128 {
+    (1u128 << n) - 1
+}
@@ -0,0 +1,135 @@
+use std::io::{self, Write};
+
+use super::{bit_reader::mask, BitOrder};
+
+/// Writes values of arbitrary bit width to given writer, in the layout `BitReader` reads.
+///
+/// Complete bytes are written immediately.
+/// The last partial byte is written (padded with zero bits) by `align()` or `into_inner()`,
+/// and is lost if the writer is just dropped.
+///
+/// # Examples
+///
+/// ```
+/// use lib::binary::{BitOrder, BitReader, BitWriter};
+/// use std::io;
+///
+/// fn main() -> io::Result<()> {
+///     let mut writer = BitWriter::new(vec![], BitOrder::MsbFirst);
+///     writer.write_bits(4, 4)?;
+///     writer.write_bits(5, 4)?;
+///     writer.write_bool(true)?;
+///     assert_eq!(writer.into_inner()?, [0x45, 0b1000_0000]);
+///
+///     let mut writer = BitWriter::new(vec![], BitOrder::LsbFirst);
+///     writer.write_bool(true)?;
+///     writer.write_bits(0x1FF, 9)?;
+///     let bytes = writer.into_inner()?;
+///     assert_eq!(bytes, [0xFF, 0b0000_0011]);
+///
+///     let mut reader = BitReader::new(bytes.as_slice(), BitOrder::LsbFirst);
+///     assert!(reader.read_bool()?);
+///     assert_eq!(reader.read_bits(9)?, 0x1FF);
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BitWriter<W>
+where
+    W: Write,
+{
+    writer: W,
+    order: BitOrder,
+    /// Bits not written to `writer` yet: the lowest `len` (< 8) bits, laid out as in `BitReader`.
+    buf: u128,
+    len: u32,
+}
+
+impl<W> BitWriter<W>
+where
+    W: Write,
+{
+    pub fn new(writer: W, order: BitOrder) -> Self {
+        Self {
+            writer,
+            order,
+            buf: 0,
+            len: 0,
+        }
+    }
+
+    /// Write the lowest `n` bits of `value`. Higher bits are ignored.
+    ///
+    /// # Failures
+    ///
+    /// Errors from `writer`.
+    ///
+    /// # Panics
+    ///
+    /// When `n > 64`.
+    pub fn write_bits(&mut self, value: u64, n: u32) -> io::Result<()> {
+        assert!(n <= 64, "cannot write more than 64 bits at once: {}", n);
+        let value = u128::from(value) & mask(n);
+        self.buf = match self.order {
+            BitOrder::MsbFirst => (self.buf << n) | value,
+            BitOrder::LsbFirst => self.buf | (value << self.len),
+        };
+        self.len += n;
+
+        // at most 71 bits are buffered here
+        let mut bytes = [0u8; 9];
+        let mut n_bytes = 0;
+        while self.len >= 8 {
+            self.len -= 8;
+            bytes[n_bytes] = match self.order {
+                BitOrder::MsbFirst => (self.buf >> self.len) as u8,
+                BitOrder::LsbFirst => {
+                    let byte = self.buf as u8;
+                    self.buf >>= 8;
+                    byte
+                }
+            };
+            n_bytes += 1;
+        }
+        self.buf &= mask(self.len);
+        self.writer.write_all(&bytes[..n_bytes])
+    }
+
+    /// Write 1 bit.
+    ///
+    /// # Failures
+    ///
+    /// Errors from `writer`.
+    pub fn write_bool(&mut self, value: bool) -> io::Result<()> {
+        self.write_bits(u64::from(value), 1)
+    }
+
+    /// Pad the current byte with zero bits and write it, so that the next write starts at a byte boundary.
+    ///
+    /// # Failures
+    ///
+    /// Errors from `writer`.
+    pub fn align(&mut self) -> io::Result<()> {
+        if self.is_aligned() {
+            Ok(())
+        } else {
+            self.write_bits(0, 8 - self.len)
+        }
+    }
+
+    /// Whether the next write starts at a byte boundary.
+    pub fn is_aligned(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Aligns and returns the underlying writer.
+    ///
+    /// # Failures
+    ///
+    /// Errors from `writer`.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.align()?;
+        Ok(self.writer)
+    }
+}
@@ -2,4 +2,17 @@
 pub enum Endian {
     BigEndian,
     LittleEndian,
+    /// Byte order of the target platform.
+    Native,
+}
+
+impl Endian {
+    /// Whether little endian, resolving `Native` to the target platform's byte order.
+    pub fn is_little(&self) -> bool {
+        match self {
+            Endian::BigEndian => false,
+            Endian::LittleEndian => true,
+            Endian::Native => cfg!(target_endian = "little"),
+        }
+    }
 }
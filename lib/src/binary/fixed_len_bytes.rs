@@ -1,4 +1,7 @@
-use std::{array::TryFromSliceError, convert::TryInto};
+use std::{
+    array::{self, TryFromSliceError},
+    convert::{TryFrom, TryInto},
+};
 
 /// Represents data types whose size are fixed.
 pub trait FixedLenBytes {
@@ -13,60 +16,103 @@ pub trait FixedLenBytes {
     fn new(buf: &[u8]) -> Result<Self, TryFromSliceError>
     where
         Self: Sized;
-}
-
-impl FixedLenBytes for u8 {
-    fn len() -> usize {
-        1
-    }
 
-    fn new(buf: &[u8]) -> Result<Self, TryFromSliceError>
+    /// Construct from byte sequence in little endian.
+    ///
+    /// Reverses a copy of `buf` and calls `new()` by default.
+    /// Primitive types override it to decode without the copy,
+    /// and composite types to keep the order of their elements.
+    ///
+    /// # Failures
+    ///
+    /// `TryFromSliceError` when `buf.len() != Self::len()`.
+    fn new_le(buf: &[u8]) -> Result<Self, TryFromSliceError>
     where
         Self: Sized,
     {
-        let arr = buf.try_into()?;
-        Ok(Self::from_be_bytes(arr))
+        let mut reversed = buf.to_vec();
+        reversed.reverse();
+        Self::new(&reversed)
     }
 }
 
-impl FixedLenBytes for u16 {
+macro_rules! impl_fixed_len_bytes {
+    ($($t:ty),*) => {
+        $(
+            impl FixedLenBytes for $t {
+                fn len() -> usize {
+                    std::mem::size_of::<Self>()
+                }
+
+                fn new(buf: &[u8]) -> Result<Self, TryFromSliceError>
+                where
+                    Self: Sized,
+                {
+                    let arr = buf.try_into()?;
+                    Ok(Self::from_be_bytes(arr))
+                }
+
+                fn new_le(buf: &[u8]) -> Result<Self, TryFromSliceError>
+                where
+                    Self: Sized,
+                {
+                    let arr = buf.try_into()?;
+                    Ok(Self::from_le_bytes(arr))
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_len_bytes!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+/// Elements are in order regardless of endian; each element is in the given endian.
+impl<T, const N: usize> FixedLenBytes for [T; N]
+where
+    T: FixedLenBytes,
+{
     fn len() -> usize {
-        2
+        T::len() * N
     }
 
     fn new(buf: &[u8]) -> Result<Self, TryFromSliceError>
     where
         Self: Sized,
     {
-        let arr = buf.try_into()?;
-        Ok(Self::from_be_bytes(arr))
+        array_from_elements(buf, T::new)
     }
-}
 
-impl FixedLenBytes for u32 {
-    fn len() -> usize {
-        4
-    }
-
-    fn new(buf: &[u8]) -> Result<Self, TryFromSliceError>
+    fn new_le(buf: &[u8]) -> Result<Self, TryFromSliceError>
     where
         Self: Sized,
     {
-        let arr = buf.try_into()?;
-        Ok(Self::from_be_bytes(arr))
+        array_from_elements(buf, T::new_le)
     }
 }
 
-impl FixedLenBytes for u64 {
-    fn len() -> usize {
-        8
+fn array_from_elements<T, F, const N: usize>(buf: &[u8], f: F) -> Result<[T; N], TryFromSliceError>
+where
+    T: FixedLenBytes,
+    F: Fn(&[u8]) -> Result<T, TryFromSliceError>,
+{
+    let len = T::len();
+    if buf.len() != len * N {
+        return Err(length_mismatch());
     }
-
-    fn new(buf: &[u8]) -> Result<Self, TryFromSliceError>
-    where
-        Self: Sized,
-    {
-        let arr = buf.try_into()?;
-        Ok(Self::from_be_bytes(arr))
+    // Built on the stack, not to allocate for each array read.
+    let mut error = None;
+    let elements: [Option<T>; N] = array::from_fn(|i| {
+        f(&buf[i * len..(i + 1) * len])
+            .map_err(|e| error = Some(e))
+            .ok()
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(elements.map(|e| e.unwrap_or_else(|| unreachable!("no element failed")))),
     }
 }
+
+/// `TryFromSliceError` cannot be constructed directly.
+pub(super) fn length_mismatch() -> TryFromSliceError {
+    <[u8; 1]>::try_from(&[][..]).unwrap_err()
+}
@@ -0,0 +1,77 @@
+use std::{array::TryFromSliceError, convert::TryInto};
+
+use super::{fixed_len_bytes::length_mismatch, FixedLenBytes};
+
+/// Represents data types which can be written as bytes of fixed size (`Self::len()`).
+pub trait ToFixedLenBytes: FixedLenBytes {
+    /// Write into byte sequence (in big endian).
+    ///
+    /// # Failures
+    ///
+    /// `TryFromSliceError` when `buf.len() != Self::len()`.
+    fn to_bytes(&self, buf: &mut [u8]) -> Result<(), TryFromSliceError>;
+
+    /// Write into byte sequence in little endian.
+    ///
+    /// Calls `to_bytes()` and reverses `buf` by default.
+    /// Composite types override it to keep the order of their elements.
+    ///
+    /// # Failures
+    ///
+    /// `TryFromSliceError` when `buf.len() != Self::len()`.
+    fn to_bytes_le(&self, buf: &mut [u8]) -> Result<(), TryFromSliceError> {
+        self.to_bytes(buf)?;
+        buf.reverse();
+        Ok(())
+    }
+}
+
+macro_rules! impl_to_fixed_len_bytes {
+    ($($t:ty),*) => {
+        $(
+            impl ToFixedLenBytes for $t {
+                fn to_bytes(&self, buf: &mut [u8]) -> Result<(), TryFromSliceError> {
+                    let arr: &mut [u8; std::mem::size_of::<$t>()] = buf.try_into()?;
+                    *arr = self.to_be_bytes();
+                    Ok(())
+                }
+
+                fn to_bytes_le(&self, buf: &mut [u8]) -> Result<(), TryFromSliceError> {
+                    let arr: &mut [u8; std::mem::size_of::<$t>()] = buf.try_into()?;
+                    *arr = self.to_le_bytes();
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_to_fixed_len_bytes!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl<T, const N: usize> ToFixedLenBytes for [T; N]
+where
+    T: ToFixedLenBytes,
+{
+    fn to_bytes(&self, buf: &mut [u8]) -> Result<(), TryFromSliceError> {
+        elements_to_bytes(self, buf, T::to_bytes)
+    }
+
+    fn to_bytes_le(&self, buf: &mut [u8]) -> Result<(), TryFromSliceError> {
+        elements_to_bytes(self, buf, T::to_bytes_le)
+    }
+}
+
+fn elements_to_bytes<T, F>(elements: &[T], buf: &mut [u8], f: F) -> Result<(), TryFromSliceError>
+where
+    T: ToFixedLenBytes,
+    F: Fn(&T, &mut [u8]) -> Result<(), TryFromSliceError>,
+{
+    let len = T::len();
+    if buf.len() != len * elements.len() {
+        return Err(length_mismatch());
+    }
+    for (i, element) in elements.iter().enumerate() {
+        f(element, &mut buf[i * len..(i + 1) * len])?;
+    }
+    Ok(())
+}
@@ -0,0 +1,345 @@
+//! Variable-length integers: 7 bits of payload per byte, whose top bit tells whether more bytes follow.
+
+use std::io::{self, Read, Write};
+
+use super::VarintError;
+
+/// Maximum number of bytes of a 64-bit value (`ceil(64 / 7)`).
+const MAX_LEN: usize = 10;
+
+/// Read an unsigned LEB128 integer (used by WebAssembly and DWARF), least significant group first.
+///
+/// Redundant padding bytes (`0x80`) are accepted as long as the whole encoding fits in `MAX_LEN` bytes.
+///
+/// # Failures
+///
+/// - `VarintError::Truncated` when `reader` ends in the middle.
+/// - `VarintError::Overflow` when the value needs more than 64 bits.
+///
+/// # Examples
+///
+/// ```
+/// use lib::binary::{self, VarintError};
+///
+/// fn main() -> Result<(), VarintError> {
+///     let mut bytes: &[u8] = &[0xE5, 0x8E, 0x26, 0x80, 0x00];
+///     assert_eq!(binary::read_uleb128(&mut bytes)?, 624485);
+///     // padded zero
+///     assert_eq!(binary::read_uleb128(&mut bytes)?, 0);
+///
+///     let mut encoded = vec![];
+///     binary::write_uleb128(&mut encoded, 624485)?;
+///     assert_eq!(encoded, [0xE5, 0x8E, 0x26]);
+///
+///     let mut truncated: &[u8] = &[0xE5, 0x8E];
+///     assert!(matches!(binary::read_uleb128(&mut truncated), Err(VarintError::Truncated)));
+///     let mut too_long: &[u8] = &[0xFF; 10];
+///     assert!(matches!(binary::read_uleb128(&mut too_long), Err(VarintError::Overflow)));
+///
+///     Ok(())
+/// }
+/// ```
+pub fn read_uleb128<R>(reader: &mut R) -> Result<u64, VarintError>
+where
+    R: Read,
+{
+    let mut value = 0u64;
+    for i in 0..MAX_LEN {
+        let byte = read_byte(reader)?;
+        let payload = u64::from(byte & 0x7F);
+        let shift = 7 * i;
+        // only 1 bit is left for the last byte
+        if shift == 63 && payload > 1 {
+            return Err(VarintError::Overflow);
+        }
+        value |= payload << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(VarintError::Overflow)
+}
+
+/// Write `value` as an unsigned LEB128 integer in the shortest form.
+pub fn write_uleb128<W>(writer: &mut W, mut value: u64) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut buf = [0u8; MAX_LEN];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    writer.write_all(&buf[..len])
+}
+
+/// Read a signed LEB128 integer, in two's complement sign-extended from the last group.
+///
+/// # Failures
+///
+/// - `VarintError::Truncated` when `reader` ends in the middle.
+/// - `VarintError::Overflow` when the value does not fit in `i64`.
+///
+/// # Examples
+///
+/// ```
+/// use lib::binary::{self, VarintError};
+///
+/// fn main() -> Result<(), VarintError> {
+///     let mut bytes: &[u8] = &[0xC0, 0xBB, 0x78, 0x7F];
+///     assert_eq!(binary::read_sleb128(&mut bytes)?, -123456);
+///     assert_eq!(binary::read_sleb128(&mut bytes)?, -1);
+///
+///     let mut encoded = vec![];
+///     binary::write_sleb128(&mut encoded, -123456)?;
+///     binary::write_sleb128(&mut encoded, 64)?;
+///     assert_eq!(encoded, [0xC0, 0xBB, 0x78, 0xC0, 0x00]);
+///
+///     for n in [i64::MIN, i64::MAX] {
+///         let mut encoded = vec![];
+///         binary::write_sleb128(&mut encoded, n)?;
+///         assert_eq!(binary::read_sleb128(&mut encoded.as_slice())?, n);
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub fn read_sleb128<R>(reader: &mut R) -> Result<i64, VarintError>
+where
+    R: Read,
+{
+    let mut value = 0i64;
+    for i in 0..MAX_LEN {
+        let byte = read_byte(reader)?;
+        let shift = 7 * i;
+        // the last byte has 1 bit of value and the rest must be its sign extension
+        if shift == 63 && byte != 0x00 && byte != 0x7F {
+            return Err(VarintError::Overflow);
+        }
+        value |= i64::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            let shift = shift + 7;
+            if shift < 64 && byte & 0x40 != 0 {
+                value |= -1 << shift;
+            }
+            return Ok(value);
+        }
+    }
+    Err(VarintError::Overflow)
+}
+
+/// Write `value` as a signed LEB128 integer in the shortest form.
+pub fn write_sleb128<W>(writer: &mut W, mut value: i64) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut buf = [0u8; MAX_LEN];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7F) as u8;
+        // arithmetic shift keeps the sign
+        value >>= 7;
+        let sign_bit = byte & 0x40 != 0;
+        if (value == 0 && !sign_bit) || (value == -1 && sign_bit) {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    writer.write_all(&buf[..len])
+}
+
+/// Read a protocol buffers varint (`uint64` etc.).
+///
+/// The encoding is the same as unsigned LEB128.
+///
+/// # Failures
+///
+/// - `VarintError::Truncated` when `reader` ends in the middle.
+/// - `VarintError::Overflow` when the varint is longer than 10 bytes or exceeds 64 bits.
+///
+/// # Examples
+///
+/// ```
+/// use lib::binary::{self, VarintError};
+///
+/// fn main() -> Result<(), VarintError> {
+///     // field 1, wire type 0 (varint), value 150
+///     let mut bytes: &[u8] = &[0x08, 0x96, 0x01];
+///     let key = binary::read_varint(&mut bytes)?;
+///     assert_eq!((key >> 3, key & 0x07), (1, 0));
+///     assert_eq!(binary::read_varint(&mut bytes)?, 150);
+///
+///     // `int64` fields encode negative values in 10 bytes
+///     let mut encoded = vec![];
+///     binary::write_varint(&mut encoded, -1i64 as u64)?;
+///     assert_eq!(encoded.len(), 10);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn read_varint<R>(reader: &mut R) -> Result<u64, VarintError>
+where
+    R: Read,
+{
+    read_uleb128(reader)
+}
+
+/// Write `value` as a protocol buffers varint.
+pub fn write_varint<W>(writer: &mut W, value: u64) -> io::Result<()>
+where
+    W: Write,
+{
+    write_uleb128(writer, value)
+}
+
+/// Read a zigzag-encoded protocol buffers varint (`sint64` etc.).
+///
+/// # Failures
+///
+/// Same as `read_varint()`.
+///
+/// # Examples
+///
+/// ```
+/// use lib::binary::{self, VarintError};
+///
+/// fn main() -> Result<(), VarintError> {
+///     let mut encoded = vec![];
+///     binary::write_zigzag(&mut encoded, -1)?;
+///     binary::write_zigzag(&mut encoded, 1)?;
+///     binary::write_zigzag(&mut encoded, -64)?;
+///     // small magnitudes stay short regardless of the sign
+///     assert_eq!(encoded, [0x01, 0x02, 0x7F]);
+///
+///     let mut bytes = encoded.as_slice();
+///     assert_eq!(binary::read_zigzag(&mut bytes)?, -1);
+///     assert_eq!(binary::read_zigzag(&mut bytes)?, 1);
+///     assert_eq!(binary::read_zigzag(&mut bytes)?, -64);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn read_zigzag<R>(reader: &mut R) -> Result<i64, VarintError>
+where
+    R: Read,
+{
+    read_varint(reader).map(zigzag_decode)
+}
+
+/// Write `value` as a zigzag-encoded protocol buffers varint.
+pub fn write_zigzag<W>(writer: &mut W, value: i64) -> io::Result<()>
+where
+    W: Write,
+{
+    write_varint(writer, zigzag_encode(value))
+}
+
+/// Maps signed integers to unsigned ones alternately: 0, -1, 1, -2, ... to 0, 1, 2, 3, ...
+///
+/// # Examples
+///
+/// ```
+/// use lib::binary;
+///
+/// assert_eq!(binary::zigzag_encode(-2), 3);
+/// assert_eq!(binary::zigzag_encode(i64::MIN), u64::MAX);
+/// assert_eq!(binary::zigzag_decode(binary::zigzag_encode(i64::MAX)), i64::MAX);
+/// ```
+pub fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Inverse of `zigzag_encode()`.
+pub fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+/// Read an offset varint of Git packfiles (base offset of `OFS_DELTA` objects),
+/// most significant group first.
+///
+/// Unlike LEB128, each continuation adds 1 before shifting, so every value has exactly one encoding.
+///
+/// # Failures
+///
+/// - `VarintError::Truncated` when `reader` ends in the middle.
+/// - `VarintError::Overflow` when the value exceeds 64 bits.
+///
+/// # Examples
+///
+/// ```
+/// use lib::binary::{self, VarintError};
+///
+/// fn main() -> Result<(), VarintError> {
+///     let mut bytes: &[u8] = &[0x7F, 0x80, 0x00, 0x91, 0x2E];
+///     assert_eq!(binary::read_offset_varint(&mut bytes)?, 127);
+///     assert_eq!(binary::read_offset_varint(&mut bytes)?, 128);
+///     assert_eq!(binary::read_offset_varint(&mut bytes)?, 2350);
+///
+///     let mut encoded = vec![];
+///     binary::write_offset_varint(&mut encoded, 2350)?;
+///     assert_eq!(encoded, [0x91, 0x2E]);
+///
+///     let mut encoded = vec![];
+///     binary::write_offset_varint(&mut encoded, u64::MAX)?;
+///     assert_eq!(binary::read_offset_varint(&mut encoded.as_slice())?, u64::MAX);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn read_offset_varint<R>(reader: &mut R) -> Result<u64, VarintError>
+where
+    R: Read,
+{
+    let mut byte = read_byte(reader)?;
+    let mut value = u64::from(byte & 0x7F);
+    while byte & 0x80 != 0 {
+        byte = read_byte(reader)?;
+        value = value
+            .checked_add(1)
+            .and_then(|v| v.checked_mul(0x80))
+            .ok_or(VarintError::Overflow)?
+            | u64::from(byte & 0x7F);
+    }
+    Ok(value)
+}
+
+/// Write `value` as an offset varint of Git packfiles.
+pub fn write_offset_varint<W>(writer: &mut W, mut value: u64) -> io::Result<()>
+where
+    W: Write,
+{
+    // filled from the end since the least significant group comes last
+    let mut buf = [0u8; MAX_LEN];
+    let mut pos = MAX_LEN - 1;
+    buf[pos] = (value & 0x7F) as u8;
+    value >>= 7;
+    while value != 0 {
+        value -= 1;
+        pos -= 1;
+        buf[pos] = 0x80 | (value & 0x7F) as u8;
+        value >>= 7;
+    }
+    writer.write_all(&buf[pos..])
+}
+
+fn read_byte<R>(reader: &mut R) -> Result<u8, VarintError>
+where
+    R: Read,
+{
+    let mut buf = [0u8; 1];
+    reader
+        .read_exact(&mut buf)
+        .map_err(VarintError::from_read_error)?;
+    Ok(buf[0])
+}
@@ -0,0 +1,57 @@
+use std::{error::Error, fmt::Display, io};
+
+/// Errors while reading variable-length integers.
+#[derive(Debug)]
+pub enum VarintError {
+    /// Input ended before the last byte (whose continuation bit is clear).
+    Truncated,
+    /// Encoded value does not fit in 64 bits.
+    Overflow,
+    IoError(io::Error),
+}
+
+impl VarintError {
+    /// Maps unexpected EOF to `VarintError::Truncated`.
+    pub(super) fn from_read_error(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => VarintError::Truncated,
+            _ => VarintError::IoError(error),
+        }
+    }
+}
+
+impl From<io::Error> for VarintError {
+    fn from(error: io::Error) -> Self {
+        VarintError::IoError(error)
+    }
+}
+
+/// So that varints can be read along with `binary::read()` in functions returning `io::Result`.
+impl From<VarintError> for io::Error {
+    fn from(error: VarintError) -> Self {
+        match error {
+            VarintError::Truncated => io::Error::new(io::ErrorKind::UnexpectedEof, error),
+            VarintError::Overflow => io::Error::new(io::ErrorKind::InvalidData, error),
+            VarintError::IoError(e) => e,
+        }
+    }
+}
+
+impl Display for VarintError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VarintError::Truncated => write!(f, "varint is truncated"),
+            VarintError::Overflow => write!(f, "varint overflows 64 bits"),
+            VarintError::IoError(e) => write!(f, "IO error: {}", e),
+        }
+    }
+}
+
+impl Error for VarintError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            VarintError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,240 @@
+mod directive;
+mod from_scan_fields;
+mod input;
+mod scan_field;
+mod scanf_error;
+
+pub use from_scan_fields::FromScanFields;
+pub use scan_field::ScanField;
+pub use scanf_error::ScanfError;
+
+use std::io::BufRead;
+
+use directive::{Conversion, Directive};
+use input::Input;
+
+/// Reads values from `reader` as directed by `format`, like C's scanf.
+///
+/// `format` consists of:
+///
+/// - `%d`: integer (optional sign and digits).
+/// - `%f`: floating point number in decimal or exponential notation.
+/// - `%s` or `{}`: characters up to whitespace (including literals that follow it in `format`).
+/// - `%c`: a character, including whitespace.
+/// - Width such as `%5d` or `{:5}`: reads at most 5 characters (`%5c` reads 5 characters).
+/// - Whitespace: skips 0 or more whitespace. Fields other than `%c` also skip leading whitespace.
+/// - Other characters: must appear as is. `%%`, `{{` and `}}` are literal `%`, `{` and `}`.
+///
+/// Each field is parsed to the corresponding element of the result tuple by `FromStr`.
+/// Only bytes up to the end of the last directive are consumed from `reader`.
+///
+/// # Failures
+///
+/// - `ScanfError::InvalidFormat` or `ScanfError::FieldCount` before reading anything when `format` is wrong.
+/// - `ScanfError::LiteralMismatch`, `ScanfError::MissingField` or `ScanfError::InvalidField`
+///   with the failing field and its byte position in the input.
+/// - `ScanfError::IoError` from `reader`.
+///
+/// # Examples
+///
+/// ```
+/// use lib::fmt::{self, ScanfError};
+///
+/// fn main() -> Result<(), ScanfError> {
+///     let (i, f, g, s): (i32, f64, f64, String) =
+///         fmt::scan(&mut "123 1.234 1.0e4 test".as_bytes(), "%d %f %f %s")?;
+///     assert_eq!((i, f, g, s.as_str()), (123, 1.234, 1.0e4, "test"));
+///
+///     // literals and widths
+///     let (x, y): (i64, i64) = fmt::scan(&mut "x=12,y=-3".as_bytes(), "x=%d,y=%d")?;
+///     assert_eq!((x, y), (12, -3));
+///     let (key, value): (String, u32) = fmt::scan(&mut "answer = 42".as_bytes(), "{} = {}")?;
+///     assert_eq!((key.as_str(), value), ("answer", 42));
+///     let date: (u16, u8, u8) = fmt::scan(&mut "20210601".as_bytes(), "%4d%2d%2d")?;
+///     assert_eq!(date, (2021, 6, 1));
+///
+///     // reads line after line from the same reader
+///     let mut reader = "1 2\n3 4\n".as_bytes();
+///     let mut sum = 0;
+///     for _ in 0..2 {
+///         let (a, b): (i32, i32) = fmt::scan(&mut reader, "%d %d")?;
+///         sum += a + b;
+///     }
+///     assert_eq!(sum, 10);
+///
+///     // errors tell which field failed and where
+///     let err = fmt::scan::<_, (i32, i32)>(&mut "x=12,y=abc".as_bytes(), "x=%d,y=%d").unwrap_err();
+///     assert_eq!(err.to_string(), "field #1 (%d) is missing at byte 7, found 'a'");
+///     let err = fmt::scan::<_, (u8,)>(&mut "  300".as_bytes(), "%d").unwrap_err();
+///     assert_eq!(
+///         err.to_string(),
+///         r#"field #0 (%d) at byte 2 is invalid: "300": number too large to fit in target type"#
+///     );
+///
+///     // a mismatching literal is not consumed, even when it is multi-byte
+///     let mut reader = "éx".as_bytes();
+///     let err = fmt::scan::<_, (String,)>(&mut reader, "è%s").unwrap_err();
+///     assert_eq!(err.to_string(), "expected 'è' at byte 0, found 'é'");
+///     assert_eq!(reader, "éx".as_bytes());
+///
+///     // width must be positive
+///     let err = fmt::scan::<_, (i32,)>(&mut "1".as_bytes(), "%0d").unwrap_err();
+///     assert!(matches!(err, ScanfError::InvalidFormat(_)));
+///
+///     Ok(())
+/// }
+/// ```
+pub fn scan<R, T>(reader: &mut R, format: &str) -> Result<T, ScanfError>
+where
+    R: BufRead,
+    T: FromScanFields,
+{
+    let directives = Directive::parse_format(format)?;
+    let n_fields = directives
+        .iter()
+        .filter(|d| matches!(d, Directive::Field { .. }))
+        .count();
+    if n_fields != T::LEN {
+        return Err(ScanfError::FieldCount {
+            format: n_fields,
+            result: T::LEN,
+        });
+    }
+    let fields = scan_directives(reader, &directives)?;
+    T::from_scan_fields(fields)
+}
+
+/// Same as `scan()` but returns the text of fields without parsing them,
+/// for formats with more than 8 fields or types without `FromStr`.
+///
+/// # Failures
+///
+/// Same as `scan()`, except for `ScanfError::FieldCount` and `ScanfError::InvalidField`
+/// (which `ScanField::parse()` returns).
+///
+/// # Examples
+///
+/// ```
+/// use lib::fmt::{self, ScanfError};
+///
+/// fn main() -> Result<(), ScanfError> {
+///     let fields = fmt::scan_fields(&mut "key = value".as_bytes(), "%s = %s")?;
+///     assert_eq!(fields[1].text, "value");
+///     assert_eq!(fields[1].position, 6);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn scan_fields<R>(reader: &mut R, format: &str) -> Result<Vec<ScanField>, ScanfError>
+where
+    R: BufRead,
+{
+    let directives = Directive::parse_format(format)?;
+    scan_directives(reader, &directives)
+}
+
+fn scan_directives<R>(
+    reader: &mut R,
+    directives: &[Directive],
+) -> Result<Vec<ScanField>, ScanfError>
+where
+    R: BufRead,
+{
+    let mut input = Input::new(reader);
+    let mut fields = vec![];
+    for directive in directives {
+        match directive {
+            Directive::Whitespace => input.skip_whitespace()?,
+            Directive::Literal(c) => {
+                let mut buf = [0u8; 4];
+                let position = input.position;
+                if !input.consume_if(c.encode_utf8(&mut buf).as_bytes())? {
+                    return Err(ScanfError::LiteralMismatch {
+                        position,
+                        expected: *c,
+                        found: input.peek_char()?,
+                    });
+                }
+            }
+            Directive::Field {
+                conversion,
+                width,
+                text: directive,
+            } => {
+                if *conversion != Conversion::Char {
+                    input.skip_whitespace()?;
+                }
+                let position = input.position;
+                let bytes = match conversion {
+                    Conversion::Integer => input.take_while(*width, integer_acceptor())?,
+                    Conversion::Float => input.take_while(*width, float_acceptor())?,
+                    Conversion::Word => input.take_while(*width, |b| !b.is_ascii_whitespace())?,
+                    Conversion::Char => input.take_while(Some(width.unwrap_or(1)), |_| true)?,
+                };
+                if bytes.is_empty() {
+                    return Err(ScanfError::MissingField {
+                        field: fields.len(),
+                        directive: directive.clone(),
+                        position,
+                        found: input.peek_char()?,
+                    });
+                }
+                let text = String::from_utf8(bytes).map_err(|e| ScanfError::InvalidField {
+                    field: fields.len(),
+                    directive: directive.clone(),
+                    position,
+                    text: String::from_utf8_lossy(e.as_bytes()).into_owned(),
+                    message: e.utf8_error().to_string(),
+                })?;
+                fields.push(ScanField {
+                    index: fields.len(),
+                    directive: directive.clone(),
+                    position,
+                    text,
+                });
+            }
+        }
+    }
+    Ok(fields)
+}
+
+/// Accepts an optional sign followed by digits.
+fn integer_acceptor() -> impl FnMut(u8) -> bool {
+    let mut first = true;
+    move |b| {
+        let accept = b.is_ascii_digit() || (first && (b == b'+' || b == b'-'));
+        first = false;
+        accept
+    }
+}
+
+/// Accepts characters of a number such as "-1.5e+3" in order.
+/// Incomplete numbers (e.g. "1e") are left for `FromStr` to reject.
+fn float_acceptor() -> impl FnMut(u8) -> bool {
+    let mut prev = None;
+    let mut seen_digit = false;
+    let mut seen_dot = false;
+    let mut seen_exp = false;
+    move |b| {
+        let accept = match b {
+            b'0'..=b'9' => {
+                seen_digit = true;
+                true
+            }
+            b'+' | b'-' => matches!(prev, None | Some(b'e') | Some(b'E')),
+            b'.' if !seen_dot && !seen_exp => {
+                seen_dot = true;
+                true
+            }
+            b'e' | b'E' if seen_digit && !seen_exp => {
+                seen_exp = true;
+                true
+            }
+            _ => false,
+        };
+        if accept {
+            prev = Some(b);
+        }
+        accept
+    }
+}
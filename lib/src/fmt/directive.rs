@@ -0,0 +1,121 @@
+use super::ScanfError;
+
+/// Element of a format string.
+#[derive(Debug)]
+pub(super) enum Directive {
+    /// Whitespace, matching 0 or more whitespace in the input.
+    Whitespace,
+    Literal(char),
+    Field {
+        conversion: Conversion,
+        /// Maximum number of characters.
+        width: Option<usize>,
+        /// As written in the format string, such as "%5d".
+        text: String,
+    },
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub(super) enum Conversion {
+    /// `%d`: optional sign and digits.
+    Integer,
+    /// `%f`: decimal or exponential notation.
+    Float,
+    /// `%s` and `{}`: characters up to whitespace.
+    Word,
+    /// `%c`: any characters (1 by default), without skipping whitespace.
+    Char,
+}
+
+impl Directive {
+    /// # Failures
+    ///
+    /// `ScanfError::InvalidFormat` on unknown conversions or unclosed placeholders.
+    pub(super) fn parse_format(format: &str) -> Result<Vec<Directive>, ScanfError> {
+        let mut directives = vec![];
+        let mut rest = format;
+        while let Some(c) = rest.chars().next() {
+            let len = if c.is_ascii_whitespace() {
+                directives.push(Directive::Whitespace);
+                rest.len()
+                    - rest
+                        .trim_start_matches(|c: char| c.is_ascii_whitespace())
+                        .len()
+            } else if rest.starts_with("%%") || rest.starts_with("{{") || rest.starts_with("}}") {
+                directives.push(Directive::Literal(c));
+                2
+            } else if c == '%' {
+                let digits = count_digits(&rest[1..]);
+                let conversion = match rest[1 + digits..].chars().next() {
+                    Some('d') => Conversion::Integer,
+                    Some('f') => Conversion::Float,
+                    Some('s') => Conversion::Word,
+                    Some('c') => Conversion::Char,
+                    Some(c) => return Err(invalid_format(&format!("unknown conversion %{}", c))),
+                    None => return Err(invalid_format("format ends with %")),
+                };
+                let len = 1 + digits + 1;
+                directives.push(Directive::field(
+                    conversion,
+                    &rest[..len],
+                    &rest[1..1 + digits],
+                )?);
+                len
+            } else if c == '{' {
+                // "{:5}"
+                let width_start = if rest[1..].starts_with(':') { 2 } else { 1 };
+                let digits = count_digits(&rest[width_start..]);
+                let close = width_start + digits;
+                if !rest[close..].starts_with('}') {
+                    return Err(invalid_format(
+                        "{ is not closed by } (write {{ for a literal)",
+                    ));
+                }
+                let width = &rest[width_start..close];
+                directives.push(Directive::field(
+                    Conversion::Word,
+                    &rest[..close + 1],
+                    width,
+                )?);
+                close + 1
+            } else if c == '}' {
+                return Err(invalid_format("unmatched } (write }} for a literal)"));
+            } else {
+                directives.push(Directive::Literal(c));
+                c.len_utf8()
+            };
+            rest = &rest[len..];
+        }
+        Ok(directives)
+    }
+
+    fn field(conversion: Conversion, text: &str, width: &str) -> Result<Self, ScanfError> {
+        let width = if width.is_empty() {
+            None
+        } else {
+            let width = width
+                .parse()
+                .map_err(|_| invalid_format(&format!("width of {} is too large", text)))?;
+            if width == 0 {
+                return Err(invalid_format(&format!(
+                    "width of {} must be positive",
+                    text
+                )));
+            }
+            Some(width)
+        };
+        Ok(Directive::Field {
+            conversion,
+            width,
+            text: text.to_string(),
+        })
+    }
+}
+
+fn count_digits(s: &str) -> usize {
+    s.bytes().take_while(u8::is_ascii_digit).count()
+}
+
+fn invalid_format(message: &str) -> ScanfError {
+    ScanfError::InvalidFormat(message.to_string())
+}
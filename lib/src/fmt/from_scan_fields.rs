@@ -0,0 +1,45 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::{ScanField, ScanfError};
+
+/// Represents types built from fields scanned by `fmt::scan()`.
+///
+/// Implemented for tuples of up to 8 `FromStr` types.
+pub trait FromScanFields: Sized {
+    /// Number of fields.
+    const LEN: usize;
+
+    /// `fields` has exactly `Self::LEN` fields.
+    ///
+    /// # Failures
+    ///
+    /// `ScanfError::InvalidField` when a field cannot be parsed.
+    fn from_scan_fields(fields: Vec<ScanField>) -> Result<Self, ScanfError>;
+}
+
+macro_rules! impl_from_scan_fields {
+    ($len:expr; $($t:ident),+) => {
+        impl<$($t),+> FromScanFields for ($($t,)+)
+        where
+            $($t: FromStr, $t::Err: Display,)+
+        {
+            const LEN: usize = $len;
+
+            fn from_scan_fields(fields: Vec<ScanField>) -> Result<Self, ScanfError> {
+                let mut fields = fields.iter();
+                Ok(($(
+                    fields.next().expect("number of fields is checked").parse::<$t>()?,
+                )+))
+            }
+        }
+    };
+}
+
+impl_from_scan_fields!(1; A);
+impl_from_scan_fields!(2; A, B);
+impl_from_scan_fields!(3; A, B, C);
+impl_from_scan_fields!(4; A, B, C, D);
+impl_from_scan_fields!(5; A, B, C, D, E);
+impl_from_scan_fields!(6; A, B, C, D, E, F);
+impl_from_scan_fields!(7; A, B, C, D, E, F, G);
+impl_from_scan_fields!(8; A, B, C, D, E, F, G, H);
@@ -0,0 +1,115 @@
+use std::{
+    io::{self, BufRead},
+    str,
+};
+
+/// `BufRead` read byte by byte, keeping the position.
+pub(super) struct Input<'r, R>
+where
+    R: BufRead,
+{
+    reader: &'r mut R,
+    /// Bytes consumed so far.
+    pub(super) position: usize,
+}
+
+impl<'r, R> Input<'r, R>
+where
+    R: BufRead,
+{
+    pub(super) fn new(reader: &'r mut R) -> Self {
+        Self {
+            reader,
+            position: 0,
+        }
+    }
+
+    /// Next byte without consuming it, or `None` at the end of input.
+    pub(super) fn peek(&mut self) -> io::Result<Option<u8>> {
+        loop {
+            match self.reader.fill_buf() {
+                Ok(buf) => return Ok(buf.first().copied()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Next character without consuming it, for error messages.
+    /// A character split across the reader's buffer is reported as U+FFFD.
+    pub(super) fn peek_char(&mut self) -> io::Result<Option<char>> {
+        self.peek()?;
+        let buf = self.reader.fill_buf()?;
+        let prefix = &buf[..buf.len().min(4)];
+        let valid = match str::from_utf8(prefix) {
+            Ok(s) => s,
+            Err(e) => str::from_utf8(&prefix[..e.valid_up_to()]).expect("validated"),
+        };
+        Ok(match valid.chars().next() {
+            Some(c) => Some(c),
+            None if prefix.is_empty() => None,
+            None => Some(char::REPLACEMENT_CHARACTER),
+        })
+    }
+
+    /// Consumes `expected` if the input starts with it, otherwise consumes nothing.
+    /// Only when `expected` is split across the reader's buffer is the part
+    /// in the current buffer consumed before the rest is compared.
+    pub(super) fn consume_if(&mut self, mut expected: &[u8]) -> io::Result<bool> {
+        while !expected.is_empty() {
+            self.peek()?;
+            let buf = self.reader.fill_buf()?;
+            let n = buf.len().min(expected.len());
+            if n == 0 || buf[..n] != expected[..n] {
+                return Ok(false);
+            }
+            self.reader.consume(n);
+            self.position += n;
+            expected = &expected[n..];
+        }
+        Ok(true)
+    }
+
+    pub(super) fn consume(&mut self) {
+        self.reader.consume(1);
+        self.position += 1;
+    }
+
+    pub(super) fn skip_whitespace(&mut self) -> io::Result<()> {
+        while let Some(b) = self.peek()? {
+            if !b.is_ascii_whitespace() {
+                break;
+            }
+            self.consume();
+        }
+        Ok(())
+    }
+
+    /// Consumes bytes while `accept` returns true, up to `max_chars` characters.
+    pub(super) fn take_while<F>(
+        &mut self,
+        max_chars: Option<usize>,
+        mut accept: F,
+    ) -> io::Result<Vec<u8>>
+    where
+        F: FnMut(u8) -> bool,
+    {
+        let mut bytes = vec![];
+        let mut n_chars = 0;
+        while let Some(b) = self.peek()? {
+            let starts_char = b & 0xC0 != 0x80;
+            if starts_char && Some(n_chars) == max_chars {
+                break;
+            }
+            if !accept(b) {
+                break;
+            }
+            if starts_char {
+                n_chars += 1;
+            }
+            bytes.push(b);
+            self.consume();
+        }
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,36 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::ScanfError;
+
+/// Text matched by a field of the format string.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ScanField {
+    /// Index of the field in the format string, from 0.
+    pub index: usize,
+    /// Field as written in the format string, such as "%5d".
+    pub directive: String,
+    /// Byte offset of `text` in the input, from where the scan started.
+    pub position: usize,
+    pub text: String,
+}
+
+impl ScanField {
+    /// # Failures
+    ///
+    /// `ScanfError::InvalidField` when `T::from_str()` fails.
+    pub fn parse<T>(&self) -> Result<T, ScanfError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .parse()
+            .map_err(|e: T::Err| ScanfError::InvalidField {
+                field: self.index,
+                directive: self.directive.clone(),
+                position: self.position,
+                text: self.text.clone(),
+                message: e.to_string(),
+            })
+    }
+}
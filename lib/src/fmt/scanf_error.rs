@@ -0,0 +1,107 @@
+use std::{error::Error, fmt::Display, io};
+
+/// Errors of `fmt::scan()`.
+/// Positions are byte offsets in the input from where the scan started.
+#[derive(Debug)]
+pub enum ScanfError {
+    /// Format string is malformed.
+    InvalidFormat(String),
+    /// Number of fields in the format string differs from that of the result type.
+    FieldCount {
+        format: usize,
+        result: usize,
+    },
+    /// Input does not match a literal character of the format string. `found` is `None` at the end of input.
+    LiteralMismatch {
+        position: usize,
+        expected: char,
+        found: Option<char>,
+    },
+    /// No characters for a field, such as letters for `%d`. `found` is `None` at the end of input.
+    MissingField {
+        field: usize,
+        directive: String,
+        position: usize,
+        found: Option<char>,
+    },
+    /// Field cannot be parsed to its type.
+    InvalidField {
+        field: usize,
+        directive: String,
+        position: usize,
+        text: String,
+        message: String,
+    },
+    IoError(io::Error),
+}
+
+impl From<io::Error> for ScanfError {
+    fn from(error: io::Error) -> Self {
+        ScanfError::IoError(error)
+    }
+}
+
+impl Display for ScanfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScanfError::InvalidFormat(message) => write!(f, "invalid format: {}", message),
+            ScanfError::FieldCount { format, result } => write!(
+                f,
+                "format has {} fields but {} values are expected",
+                format, result
+            ),
+            ScanfError::LiteralMismatch {
+                position,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected {:?} at byte {}, found {}",
+                expected,
+                position,
+                describe(found)
+            ),
+            ScanfError::MissingField {
+                field,
+                directive,
+                position,
+                found,
+            } => write!(
+                f,
+                "field #{} ({}) is missing at byte {}, found {}",
+                field,
+                directive,
+                position,
+                describe(found)
+            ),
+            ScanfError::InvalidField {
+                field,
+                directive,
+                position,
+                text,
+                message,
+            } => write!(
+                f,
+                "field #{} ({}) at byte {} is invalid: {:?}: {}",
+                field, directive, position, text, message
+            ),
+            ScanfError::IoError(e) => write!(f, "IO error: {}", e),
+        }
+    }
+}
+
+impl Error for ScanfError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScanfError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+fn describe(found: &Option<char>) -> String {
+    match found {
+        Some(c) => format!("{:?}", c),
+        None => "end of input".to_string(),
+    }
+}
@@ -1 +1,8 @@
+mod image_format;
+
+pub mod gif;
+pub mod jpeg;
 pub mod png;
+pub mod webp;
+
+pub use image_format::{detect, ImageFormat};
@@ -0,0 +1,45 @@
+//! Toolkit for analyzing GIF format.
+
+mod gif_analyzer;
+mod gif_blocks;
+mod gif_error;
+mod gif_logical_screen;
+
+use std::io::Read;
+
+use crate::binary::{self, Endian};
+
+pub use gif_analyzer::GifAnalyzer;
+pub use gif_blocks::{GifBlock, GifBlocks, ImageDescriptor, TryGifBlocks};
+pub use gif_error::GifError;
+pub use gif_logical_screen::LogicalScreen;
+
+const GIF_SIGNATURE: [u8; 3] = *b"GIF";
+const GIF_VERSIONS: [[u8; 3]; 2] = [*b"87a", *b"89a"];
+
+fn read_u8<R>(r: &mut R) -> Result<u8, GifError>
+where
+    R: Read,
+{
+    binary::read(r, &Endian::LittleEndian).map_err(GifError::from_read_error)
+}
+
+fn read_u16<R>(r: &mut R) -> Result<u16, GifError>
+where
+    R: Read,
+{
+    binary::read(r, &Endian::LittleEndian).map_err(GifError::from_read_error)
+}
+
+/// Reads color table of 2^(`size_bits` + 1) entries.
+fn read_color_table<R>(r: &mut R, size_bits: u8) -> Result<Vec<[u8; 3]>, GifError>
+where
+    R: Read,
+{
+    let mut buf = vec![0u8; 3 * (2 << (size_bits & 0x07))];
+    r.read_exact(&mut buf).map_err(GifError::from_read_error)?;
+    Ok(buf
+        .chunks_exact(3)
+        .map(|rgb| [rgb[0], rgb[1], rgb[2]])
+        .collect())
+}
@@ -0,0 +1,98 @@
+use std::io::{self, Read};
+
+use super::{GifBlocks, GifError, LogicalScreen, TryGifBlocks, GIF_SIGNATURE, GIF_VERSIONS};
+
+/// Reads GIF header, logical screen and blocks.
+///
+/// # Examples
+///
+/// ```
+/// use lib::image::gif::{GifAnalyzer, GifBlock};
+///
+/// // 1x1 image with 2-color global color table and graphic control extension
+/// let gif: &[u8] = &[
+///     b'G', b'I', b'F', b'8', b'9', b'a', 0x01, 0x00, 0x01, 0x00, 0x80, 0x00, 0x00,
+///     0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF,
+///     0x21, 0xF9, 0x04, 0x01, 0x0A, 0x00, 0x00, 0x00,
+///     0x2C, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00,
+///     0x02, 0x02, 0x44, 0x01, 0x00,
+///     0x3B,
+/// ];
+///
+/// let mut blocks = GifAnalyzer::new(gif).try_blocks().unwrap();
+/// let screen = blocks.screen();
+/// assert_eq!(&screen.version, b"89a");
+/// assert_eq!((screen.width, screen.height), (1, 1));
+/// assert_eq!(screen.global_color_table.as_ref().unwrap().len(), 2);
+///
+/// match blocks.next().unwrap().unwrap() {
+///     GifBlock::Extension { label, sub_blocks } => {
+///         assert_eq!(label, GifBlock::GRAPHIC_CONTROL_LABEL);
+///         assert_eq!(sub_blocks, [[0x01, 0x0A, 0x00, 0x00]]);
+///     }
+///     block => panic!("unexpected block: {:?}", block),
+/// }
+/// match blocks.next().unwrap().unwrap() {
+///     GifBlock::Image { descriptor, lzw_minimum_code_size, data, .. } => {
+///         assert_eq!((descriptor.width, descriptor.height), (1, 1));
+///         assert_eq!(lzw_minimum_code_size, 2);
+///         assert_eq!(data, [0x44, 0x01]);
+///     }
+///     block => panic!("unexpected block: {:?}", block),
+/// }
+/// assert!(matches!(blocks.next(), Some(Ok(GifBlock::Trailer))));
+/// assert!(blocks.next().is_none());
+/// ```
+#[derive(Debug)]
+pub struct GifAnalyzer<R>
+where
+    R: Read,
+{
+    gif_reader: R,
+}
+
+impl<R> GifAnalyzer<R>
+where
+    R: Read,
+{
+    pub fn new(gif_reader: R) -> Self {
+        Self { gif_reader }
+    }
+
+    /// Iterates blocks until the trailer or the first malformed block.
+    ///
+    /// Yields nothing when the header or logical screen descriptor is malformed.
+    pub fn blocks(self) -> GifBlocks<R> {
+        GifBlocks::new(self.try_blocks().ok())
+    }
+
+    /// Iterates blocks, reporting malformed input as `GifError`.
+    ///
+    /// # Failures
+    ///
+    /// - `GifError::BadSignature` when leading 6 bytes are neither "GIF87a" nor "GIF89a".
+    /// - `GifError::TruncatedBlock` when logical screen descriptor or global color table
+    ///   is truncated.
+    pub fn try_blocks(mut self) -> Result<TryGifBlocks<R>, GifError> {
+        let version = self.read_header()?;
+        let screen = LogicalScreen::from_reader(&mut self.gif_reader, version)?;
+        Ok(TryGifBlocks::new(self.gif_reader, screen))
+    }
+
+    /// Returns version.
+    fn read_header(&mut self) -> Result<[u8; 3], GifError> {
+        let mut header = [0u8; 6];
+        match self.gif_reader.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                return Err(GifError::BadSignature)
+            }
+            Err(e) => return Err(e.into()),
+        }
+        let (signature, version) = header.split_at(3);
+        match GIF_VERSIONS.iter().find(|v| v[..] == *version) {
+            Some(version) if signature == GIF_SIGNATURE => Ok(*version),
+            _ => Err(GifError::BadSignature),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+mod gif_block;
+
+use std::io::Read;
+
+pub use gif_block::{GifBlock, ImageDescriptor};
+
+use super::{GifError, LogicalScreen};
+
+/// Iterator of GifBlock.
+///
+/// Iteration stops after the trailer or at the first malformed block.
+/// Use `TryGifBlocks` to know why it stopped.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct GifBlocks<R>
+where
+    R: Read,
+{
+    inner: Option<TryGifBlocks<R>>,
+}
+
+impl<R> GifBlocks<R>
+where
+    R: Read,
+{
+    pub(super) fn new(inner: Option<TryGifBlocks<R>>) -> Self {
+        Self { inner }
+    }
+
+    /// None when the header was malformed.
+    pub fn screen(&self) -> Option<&LogicalScreen> {
+        self.inner.as_ref().map(TryGifBlocks::screen)
+    }
+}
+
+impl<R> Iterator for GifBlocks<R>
+where
+    R: Read,
+{
+    type Item = GifBlock;
+
+    fn next(&mut self) -> Option<GifBlock> {
+        self.inner.as_mut()?.next()?.ok()
+    }
+}
+
+/// Iterator of `Result<GifBlock, GifError>`.
+///
+/// Yields `None` after the trailer or the first error.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TryGifBlocks<R>
+where
+    R: Read,
+{
+    reader_at_next_block: R,
+    screen: LogicalScreen,
+    finished: bool,
+}
+
+impl<R> TryGifBlocks<R>
+where
+    R: Read,
+{
+    pub(super) fn new(reader_at_first_block: R, screen: LogicalScreen) -> Self {
+        Self {
+            reader_at_next_block: reader_at_first_block,
+            screen,
+            finished: false,
+        }
+    }
+
+    /// Logical screen descriptor read before the blocks.
+    pub fn screen(&self) -> &LogicalScreen {
+        &self.screen
+    }
+}
+
+impl<R> Iterator for TryGifBlocks<R>
+where
+    R: Read,
+{
+    type Item = Result<GifBlock, GifError>;
+
+    fn next(&mut self) -> Option<Result<GifBlock, GifError>> {
+        if self.finished {
+            return None;
+        }
+        let res = GifBlock::from_reader(&mut self.reader_at_next_block);
+        if !matches!(
+            res,
+            Ok(GifBlock::Extension { .. }) | Ok(GifBlock::Image { .. })
+        ) {
+            self.finished = true;
+        }
+        Some(res)
+    }
+}
@@ -0,0 +1,106 @@
+use std::io::Read;
+
+use super::super::{read_color_table, read_u16, read_u8, GifError};
+
+/// Represents a block of GIF format following the logical screen.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum GifBlock {
+    /// Extension (introduced by 0x21) such as graphic control or comment.
+    Extension {
+        label: u8,
+        /// Data sub-blocks without their size bytes.
+        sub_blocks: Vec<Vec<u8>>,
+    },
+    /// Image descriptor (introduced by 0x2C) and the image data following it.
+    Image {
+        descriptor: ImageDescriptor,
+        local_color_table: Option<Vec<[u8; 3]>>,
+        lzw_minimum_code_size: u8,
+        /// LZW-compressed pixels, concatenated from data sub-blocks.
+        data: Vec<u8>,
+    },
+    /// End of GIF data stream (0x3B).
+    Trailer,
+}
+
+/// Position and size of an image in the logical screen.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ImageDescriptor {
+    pub left: u16,
+    pub top: u16,
+    pub width: u16,
+    pub height: u16,
+    pub interlaced: bool,
+    /// Whether local color table is sorted by decreasing importance.
+    pub sorted: bool,
+}
+
+impl GifBlock {
+    pub const PLAIN_TEXT_LABEL: u8 = 0x01;
+    pub const GRAPHIC_CONTROL_LABEL: u8 = 0xF9;
+    pub const COMMENT_LABEL: u8 = 0xFE;
+    pub const APPLICATION_LABEL: u8 = 0xFF;
+
+    /// # Failures
+    ///
+    /// - `GifError::TruncatedBlock` when `r` reaches EOF before the block ends.
+    /// - `GifError::InvalidBlock` when the introducer byte is unknown.
+    pub(super) fn from_reader<R>(r: &mut R) -> Result<Self, GifError>
+    where
+        R: Read,
+    {
+        match read_u8(r)? {
+            0x21 => {
+                let label = read_u8(r)?;
+                let sub_blocks = Self::read_sub_blocks(r)?;
+                Ok(GifBlock::Extension { label, sub_blocks })
+            }
+            0x2C => {
+                let left = read_u16(r)?;
+                let top = read_u16(r)?;
+                let width = read_u16(r)?;
+                let height = read_u16(r)?;
+                let packed = read_u8(r)?;
+                let local_color_table = if packed & 0x80 != 0 {
+                    Some(read_color_table(r, packed & 0x07)?)
+                } else {
+                    None
+                };
+                let lzw_minimum_code_size = read_u8(r)?;
+                let data = Self::read_sub_blocks(r)?.concat();
+                Ok(GifBlock::Image {
+                    descriptor: ImageDescriptor {
+                        left,
+                        top,
+                        width,
+                        height,
+                        interlaced: packed & 0x40 != 0,
+                        sorted: packed & 0x20 != 0,
+                    },
+                    local_color_table,
+                    lzw_minimum_code_size,
+                    data,
+                })
+            }
+            0x3B => Ok(GifBlock::Trailer),
+            introducer => Err(GifError::InvalidBlock(introducer)),
+        }
+    }
+
+    /// Reads sub-blocks up to the block terminator (a zero size byte).
+    fn read_sub_blocks<R>(r: &mut R) -> Result<Vec<Vec<u8>>, GifError>
+    where
+        R: Read,
+    {
+        let mut sub_blocks = vec![];
+        loop {
+            let size = read_u8(r)?;
+            if size == 0 {
+                return Ok(sub_blocks);
+            }
+            let mut buf = vec![0u8; size as usize];
+            r.read_exact(&mut buf).map_err(GifError::from_read_error)?;
+            sub_blocks.push(buf);
+        }
+    }
+}
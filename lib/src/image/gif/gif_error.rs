@@ -0,0 +1,51 @@
+use std::{error::Error, fmt::Display, io};
+
+/// Errors while reading GIF format.
+#[derive(Debug)]
+pub enum GifError {
+    /// Leading 6 bytes are neither "GIF87a" nor "GIF89a".
+    BadSignature,
+    /// Input ended in the middle of a block, or before the trailer.
+    TruncatedBlock,
+    /// Block starts with an unknown introducer byte.
+    InvalidBlock(u8),
+    IoError(io::Error),
+}
+
+impl GifError {
+    /// Maps unexpected EOF to `GifError::TruncatedBlock`.
+    pub(super) fn from_read_error(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => GifError::TruncatedBlock,
+            _ => GifError::IoError(error),
+        }
+    }
+}
+
+impl From<io::Error> for GifError {
+    fn from(error: io::Error) -> Self {
+        GifError::IoError(error)
+    }
+}
+
+impl Display for GifError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GifError::BadSignature => write!(f, "wrong GIF signature"),
+            GifError::TruncatedBlock => write!(f, "GIF block is truncated"),
+            GifError::InvalidBlock(introducer) => {
+                write!(f, "invalid GIF block introducer: {:#04X}", introducer)
+            }
+            GifError::IoError(e) => write!(f, "IO error: {}", e),
+        }
+    }
+}
+
+impl Error for GifError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GifError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
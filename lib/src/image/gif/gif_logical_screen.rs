@@ -0,0 +1,50 @@
+use std::io::Read;
+
+use super::{read_color_table, read_u16, read_u8, GifError};
+
+/// Logical screen descriptor and global color table, following the GIF header.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct LogicalScreen {
+    /// "87a" or "89a".
+    pub version: [u8; 3],
+    pub width: u16,
+    pub height: u16,
+    /// Bits per primary color of the original image, 1 to 8.
+    pub color_resolution: u8,
+    /// Whether global color table is sorted by decreasing importance.
+    pub sorted: bool,
+    /// Index into global color table for pixels not covered by any image.
+    pub background_color_index: u8,
+    /// 0 when no aspect ratio is given.
+    pub pixel_aspect_ratio: u8,
+    pub global_color_table: Option<Vec<[u8; 3]>>,
+}
+
+impl LogicalScreen {
+    /// Reads logical screen descriptor and global color table from `r` just after the header.
+    pub(super) fn from_reader<R>(r: &mut R, version: [u8; 3]) -> Result<Self, GifError>
+    where
+        R: Read,
+    {
+        let width = read_u16(r)?;
+        let height = read_u16(r)?;
+        let packed = read_u8(r)?;
+        let background_color_index = read_u8(r)?;
+        let pixel_aspect_ratio = read_u8(r)?;
+        let global_color_table = if packed & 0x80 != 0 {
+            Some(read_color_table(r, packed & 0x07)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            version,
+            width,
+            height,
+            color_resolution: ((packed >> 4) & 0x07) + 1,
+            sorted: packed & 0x08 != 0,
+            background_color_index,
+            pixel_aspect_ratio,
+            global_color_table,
+        })
+    }
+}
@@ -0,0 +1,67 @@
+use std::io::{self, Read};
+
+/// Image formats `detect()` can identify.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum ImageFormat {
+    Png,
+    Gif,
+    Jpeg,
+    WebP,
+}
+
+impl ImageFormat {
+    /// File extensions in lowercase, the preferred one first.
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            ImageFormat::Png => &["png"],
+            ImageFormat::Gif => &["gif"],
+            ImageFormat::Jpeg => &["jpg", "jpeg", "jpe", "jfif"],
+            ImageFormat::WebP => &["webp"],
+        }
+    }
+
+    /// Identifies format from leading bytes of a file.
+    pub fn from_magic(header: &[u8]) -> Option<Self> {
+        if header.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+            Some(ImageFormat::Png)
+        } else if header.starts_with(b"GIF87a") || header.starts_with(b"GIF89a") {
+            Some(ImageFormat::Gif)
+        } else if header.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(ImageFormat::Jpeg)
+        } else if header.len() >= 12 && &header[..4] == b"RIFF" && &header[8..12] == b"WEBP" {
+            Some(ImageFormat::WebP)
+        } else {
+            None
+        }
+    }
+}
+
+/// Identifies image format by magic bytes, reading at most 12 bytes from `reader`.
+///
+/// # Returns
+///
+/// None when the format is unknown (including files shorter than its magic bytes).
+///
+/// # Examples
+///
+/// ```
+/// use lib::image::{detect, ImageFormat};
+///
+/// let gif: &[u8] = b"GIF89a\x01\x00\x01\x00";
+/// assert_eq!(detect(gif).unwrap(), Some(ImageFormat::Gif));
+///
+/// // JPEG content in a file named "*.png" is mislabelled
+/// let jpeg: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10];
+/// let format = detect(jpeg).unwrap().unwrap();
+/// assert!(!format.extensions().contains(&"png"));
+///
+/// assert_eq!(detect(&b"plain text"[..]).unwrap(), None);
+/// ```
+pub fn detect<R>(reader: R) -> io::Result<Option<ImageFormat>>
+where
+    R: Read,
+{
+    let mut header = Vec::<u8>::with_capacity(12);
+    reader.take(12).read_to_end(&mut header)?;
+    Ok(ImageFormat::from_magic(&header))
+}
@@ -0,0 +1,11 @@
+//! Toolkit for analyzing JPEG format.
+
+mod jpeg_analyzer;
+mod jpeg_error;
+mod jpeg_exif;
+mod jpeg_segments;
+
+pub use jpeg_analyzer::JpegAnalyzer;
+pub use jpeg_error::JpegError;
+pub use jpeg_exif::{Exif, ExifEntry, ExifIfd};
+pub use jpeg_segments::{JpegMarker, JpegSegment, JpegSegments, TryJpegSegments};
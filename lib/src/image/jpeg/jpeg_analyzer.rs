@@ -0,0 +1,76 @@
+use std::io::{self, Read};
+
+use super::{JpegError, JpegSegments, TryJpegSegments};
+
+/// Reads SOI marker and segments of JPEG.
+///
+/// # Examples
+///
+/// ```
+/// use lib::image::jpeg::{JpegAnalyzer, JpegError, JpegMarker};
+///
+/// // SOI, COM, SOS with entropy-coded data (including a stuffed byte and RST0), EOI
+/// let jpeg: &[u8] = &[
+///     0xFF, 0xD8,
+///     0xFF, 0xFE, 0x00, 0x04, b'h', b'i',
+///     0xFF, 0xDA, 0x00, 0x02, 0x12, 0xFF, 0x00, 0x34, 0xFF, 0xD0, 0x56,
+///     0xFF, 0xD9,
+/// ];
+///
+/// let segments: Vec<_> = JpegAnalyzer::new(jpeg).segments().collect();
+/// let markers: Vec<_> = segments.iter().map(|s| s.marker().to_string()).collect();
+/// assert_eq!(markers, ["COM", "SOS", "EOI"]);
+/// assert_eq!(segments[0].data(), b"hi");
+/// assert_eq!(segments[1].scan_data(), [0x12, 0xFF, 0x00, 0x34, 0xFF, 0xD0, 0x56]);
+///
+/// let mut segments = JpegAnalyzer::new(&jpeg[..10]).try_segments().unwrap();
+/// assert_eq!(segments.next().unwrap().unwrap().marker(), &JpegMarker::COM);
+/// assert!(matches!(segments.next(), Some(Err(JpegError::TruncatedSegment))));
+/// assert!(segments.next().is_none());
+/// ```
+#[derive(Debug)]
+pub struct JpegAnalyzer<R>
+where
+    R: Read,
+{
+    jpeg_reader: R,
+}
+
+impl<R> JpegAnalyzer<R>
+where
+    R: Read,
+{
+    pub fn new(jpeg_reader: R) -> Self {
+        Self { jpeg_reader }
+    }
+
+    /// Iterates segments until EOI or the first malformed segment.
+    ///
+    /// Yields nothing when SOI marker is missing.
+    pub fn segments(mut self) -> JpegSegments<R> {
+        match self.read_soi() {
+            Ok(()) => JpegSegments::new(self.jpeg_reader),
+            Err(_) => JpegSegments::empty(self.jpeg_reader),
+        }
+    }
+
+    /// Iterates segments, reporting malformed input as `JpegError`.
+    ///
+    /// # Failures
+    ///
+    /// `JpegError::BadSignature` when leading 2 bytes are not SOI marker.
+    pub fn try_segments(mut self) -> Result<TryJpegSegments<R>, JpegError> {
+        self.read_soi()?;
+        Ok(TryJpegSegments::new(self.jpeg_reader))
+    }
+
+    fn read_soi(&mut self) -> Result<(), JpegError> {
+        let mut soi = [0u8; 2];
+        match self.jpeg_reader.read_exact(&mut soi) {
+            Ok(()) if soi == [0xFF, 0xD8] => Ok(()),
+            Ok(()) => Err(JpegError::BadSignature),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Err(JpegError::BadSignature),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::{error::Error, fmt::Display, io};
+
+/// Errors while reading JPEG format.
+#[derive(Debug)]
+pub enum JpegError {
+    /// Input does not start with SOI marker.
+    BadSignature,
+    /// Input ended in the middle of a segment, or before EOI marker.
+    TruncatedSegment,
+    /// Byte other than 0xFF is found where a marker should be.
+    InvalidMarker(u8),
+    /// Segment length field is less than 2 (the length field itself).
+    InvalidSegmentLength(u16),
+    /// APP1 segment is not valid EXIF.
+    InvalidExif(&'static str),
+    IoError(io::Error),
+}
+
+impl JpegError {
+    /// Maps unexpected EOF to `JpegError::TruncatedSegment`.
+    pub(super) fn from_read_error(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => JpegError::TruncatedSegment,
+            _ => JpegError::IoError(error),
+        }
+    }
+}
+
+impl From<io::Error> for JpegError {
+    fn from(error: io::Error) -> Self {
+        JpegError::IoError(error)
+    }
+}
+
+impl Display for JpegError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JpegError::BadSignature => write!(f, "JPEG does not start with SOI marker"),
+            JpegError::TruncatedSegment => write!(f, "JPEG segment is truncated"),
+            JpegError::InvalidMarker(byte) => {
+                write!(f, "expected marker but found {:#04X}", byte)
+            }
+            JpegError::InvalidSegmentLength(len) => {
+                write!(f, "segment length too small: {}", len)
+            }
+            JpegError::InvalidExif(reason) => write!(f, "invalid EXIF: {}", reason),
+            JpegError::IoError(e) => write!(f, "IO error: {}", e),
+        }
+    }
+}
+
+impl Error for JpegError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            JpegError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
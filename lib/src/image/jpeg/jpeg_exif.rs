@@ -0,0 +1,213 @@
+use std::{convert::TryFrom, str};
+
+use crate::binary::{self, Endian, FixedLenBytes};
+
+use super::{JpegError, JpegMarker, JpegSegment};
+
+/// EXIF metadata stored in APP1 segment.
+///
+/// # Examples
+///
+/// ```
+/// use lib::image::jpeg::{Exif, JpegAnalyzer};
+///
+/// // SOI, APP1 (EXIF with IFD0 holding Make = "Go" and Orientation = 6), EOI
+/// let jpeg: &[u8] = &[
+///     0xFF, 0xD8,
+///     0xFF, 0xE1, 0x00, 0x2C, b'E', b'x', b'i', b'f', 0x00, 0x00,
+///     b'I', b'I', 0x2A, 0x00, 0x08, 0x00, 0x00, 0x00,
+///     0x02, 0x00,
+///     0x0F, 0x01, 0x02, 0x00, 0x03, 0x00, 0x00, 0x00, b'G', b'o', 0x00, 0x00,
+///     0x12, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00,
+///     0x00, 0x00, 0x00, 0x00,
+///     0xFF, 0xD9,
+/// ];
+///
+/// let app1 = JpegAnalyzer::new(jpeg)
+///     .segments()
+///     .find(|s| s.marker().is_app())
+///     .unwrap();
+/// let exif = Exif::from_segment(&app1).unwrap();
+/// assert_eq!(exif.ascii(Exif::MAKE), Some("Go"));
+/// assert_eq!(exif.unsigned(Exif::ORIENTATION), Some(6));
+/// assert_eq!(exif.unsigned(Exif::MODEL), None);
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Exif {
+    /// Byte order of the TIFF structure inside.
+    pub endian: Endian,
+    /// Entries of IFD0, Exif IFD and GPS IFD, in this order.
+    pub entries: Vec<ExifEntry>,
+}
+
+/// Image file directory an entry belongs to.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum ExifIfd {
+    /// IFD0, describing the primary image.
+    Primary,
+    /// Exif IFD, pointed by `Exif::EXIF_IFD_POINTER`.
+    Exif,
+    /// GPS IFD, pointed by `Exif::GPS_IFD_POINTER`.
+    Gps,
+}
+
+/// Tagged value in an IFD.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ExifEntry {
+    pub ifd: ExifIfd,
+    pub tag: u16,
+    /// Type of values: 1 = BYTE, 2 = ASCII, 3 = SHORT, 4 = LONG, 5 = RATIONAL, and so on.
+    pub format: u16,
+    /// Number of values.
+    pub count: u32,
+    /// Raw values in `Exif::endian` order.
+    /// The 4-byte value field itself when `format` is unknown.
+    pub value: Vec<u8>,
+}
+
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+
+impl Exif {
+    pub const MAKE: u16 = 0x010F;
+    pub const MODEL: u16 = 0x0110;
+    pub const ORIENTATION: u16 = 0x0112;
+    pub const DATE_TIME: u16 = 0x0132;
+    pub const EXIF_IFD_POINTER: u16 = 0x8769;
+    pub const GPS_IFD_POINTER: u16 = 0x8825;
+    pub const DATE_TIME_ORIGINAL: u16 = 0x9003;
+
+    /// # Failures
+    ///
+    /// `JpegError::InvalidExif` when `segment` is not APP1 starting with "Exif\0\0",
+    /// or its TIFF structure is broken.
+    pub fn from_segment(segment: &JpegSegment) -> Result<Self, JpegError> {
+        if segment.marker() != &JpegMarker::APP1 {
+            return Err(JpegError::InvalidExif("not APP1 segment"));
+        }
+        let tiff = segment
+            .data()
+            .strip_prefix(EXIF_HEADER)
+            .ok_or(JpegError::InvalidExif("missing Exif header"))?;
+        let endian = match tiff.get(..2) {
+            Some(b"II") => Endian::LittleEndian,
+            Some(b"MM") => Endian::BigEndian,
+            _ => return Err(JpegError::InvalidExif("unknown byte order")),
+        };
+        let tiff = Tiff {
+            bytes: tiff,
+            endian,
+        };
+        if tiff.read::<u16>(2)? != 42 {
+            return Err(JpegError::InvalidExif("wrong TIFF magic number"));
+        }
+
+        let mut entries = tiff.ifd(tiff.read::<u32>(4)?, ExifIfd::Primary)?;
+        for &(pointer, ifd) in &[
+            (Self::EXIF_IFD_POINTER, ExifIfd::Exif),
+            (Self::GPS_IFD_POINTER, ExifIfd::Gps),
+        ] {
+            let offset = entries
+                .iter()
+                .find(|e| e.tag == pointer)
+                .and_then(|e| first_unsigned(e, &tiff.endian));
+            if let Some(offset) = offset {
+                entries.extend(tiff.ifd(offset, ifd)?);
+            }
+        }
+
+        Ok(Self {
+            endian: tiff.endian,
+            entries,
+        })
+    }
+
+    /// First entry of `tag` in any IFD.
+    pub fn get(&self, tag: u16) -> Option<&ExifEntry> {
+        self.entries.iter().find(|e| e.tag == tag)
+    }
+
+    /// Value of ASCII entry without trailing null characters.
+    pub fn ascii(&self, tag: u16) -> Option<&str> {
+        let entry = self.get(tag).filter(|e| e.format == 2)?;
+        str::from_utf8(&entry.value)
+            .ok()
+            .map(|s| s.trim_end_matches('\0'))
+    }
+
+    /// First value of BYTE, SHORT or LONG entry.
+    pub fn unsigned(&self, tag: u16) -> Option<u32> {
+        first_unsigned(self.get(tag)?, &self.endian)
+    }
+}
+
+fn first_unsigned(entry: &ExifEntry, endian: &Endian) -> Option<u32> {
+    let mut value = entry.value.as_slice();
+    match entry.format {
+        1 => value.first().map(|b| *b as u32),
+        3 => binary::read::<_, u16>(&mut value, endian)
+            .ok()
+            .map(|n| n as u32),
+        4 => binary::read(&mut value, endian).ok(),
+        _ => None,
+    }
+}
+
+/// Bytes per value of `format`.
+fn format_size(format: u16) -> Option<usize> {
+    match format {
+        1 | 2 | 6 | 7 => Some(1),
+        3 | 8 => Some(2),
+        4 | 9 | 11 => Some(4),
+        5 | 10 | 12 => Some(8),
+        _ => None,
+    }
+}
+
+/// TIFF structure whose offsets are relative to its start.
+struct Tiff<'a> {
+    bytes: &'a [u8],
+    endian: Endian,
+}
+
+impl<'a> Tiff<'a> {
+    fn slice(&self, offset: usize, len: usize) -> Result<&'a [u8], JpegError> {
+        offset
+            .checked_add(len)
+            .and_then(|end| self.bytes.get(offset..end))
+            .ok_or(JpegError::InvalidExif("offset out of range"))
+    }
+
+    fn read<B>(&self, offset: usize) -> Result<B, JpegError>
+    where
+        B: FixedLenBytes,
+    {
+        let mut bytes = self.slice(offset, B::len())?;
+        Ok(binary::read(&mut bytes, &self.endian)?)
+    }
+
+    fn ifd(&self, offset: u32, ifd: ExifIfd) -> Result<Vec<ExifEntry>, JpegError> {
+        let offset = offset as usize;
+        let count = self.read::<u16>(offset)? as usize;
+        (0..count)
+            .map(|i| {
+                let at = offset + 2 + 12 * i;
+                let format = self.read::<u16>(at + 2)?;
+                let count = self.read::<u32>(at + 4)?;
+                let size = format_size(format)
+                    .and_then(|size| size.checked_mul(usize::try_from(count).ok()?));
+                let value = match size {
+                    Some(size) if size <= 4 => self.slice(at + 8, size)?,
+                    Some(size) => self.slice(self.read::<u32>(at + 8)? as usize, size)?,
+                    None => self.slice(at + 8, 4)?,
+                };
+                Ok(ExifEntry {
+                    ifd,
+                    tag: self.read(at)?,
+                    format,
+                    count,
+                    value: value.to_vec(),
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,105 @@
+mod jpeg_segment;
+
+use std::io::Read;
+
+pub use jpeg_segment::{JpegMarker, JpegSegment};
+
+use super::JpegError;
+
+/// Iterator of JpegSegment.
+///
+/// Iteration stops after EOI or at the first malformed segment.
+/// Use `TryJpegSegments` to know why it stopped.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct JpegSegments<R>
+where
+    R: Read,
+{
+    inner: TryJpegSegments<R>,
+}
+
+impl<R> JpegSegments<R>
+where
+    R: Read,
+{
+    pub fn new(reader_after_soi: R) -> Self {
+        Self {
+            inner: TryJpegSegments::new(reader_after_soi),
+        }
+    }
+
+    /// Iterator yielding nothing.
+    pub(super) fn empty(reader: R) -> Self {
+        let mut inner = TryJpegSegments::new(reader);
+        inner.finished = true;
+        Self { inner }
+    }
+}
+
+impl<R> Iterator for JpegSegments<R>
+where
+    R: Read,
+{
+    type Item = JpegSegment;
+
+    fn next(&mut self) -> Option<JpegSegment> {
+        self.inner.next()?.ok()
+    }
+}
+
+/// Iterator of `Result<JpegSegment, JpegError>`.
+///
+/// Segments start after SOI; the last one is EOI.
+/// Yields `None` after EOI or the first error.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TryJpegSegments<R>
+where
+    R: Read,
+{
+    reader_at_next_segment: R,
+    /// Marker already read at the end of entropy-coded data.
+    pending_marker: Option<JpegMarker>,
+    finished: bool,
+}
+
+impl<R> TryJpegSegments<R>
+where
+    R: Read,
+{
+    pub fn new(reader_after_soi: R) -> Self {
+        Self {
+            reader_at_next_segment: reader_after_soi,
+            pending_marker: None,
+            finished: false,
+        }
+    }
+
+    fn read_segment(&mut self) -> Result<JpegSegment, JpegError> {
+        let marker = match self.pending_marker.take() {
+            Some(marker) => marker,
+            None => JpegSegment::read_marker(&mut self.reader_at_next_segment)?,
+        };
+        let (segment, next_marker) =
+            JpegSegment::from_reader(&mut self.reader_at_next_segment, marker)?;
+        self.pending_marker = next_marker;
+        Ok(segment)
+    }
+}
+
+impl<R> Iterator for TryJpegSegments<R>
+where
+    R: Read,
+{
+    type Item = Result<JpegSegment, JpegError>;
+
+    fn next(&mut self) -> Option<Result<JpegSegment, JpegError>> {
+        if self.finished {
+            return None;
+        }
+        let res = self.read_segment();
+        if !matches!(&res, Ok(segment) if segment.marker() != &JpegMarker::EOI) {
+            self.finished = true;
+        }
+        Some(res)
+    }
+}
@@ -0,0 +1,123 @@
+mod jpeg_marker;
+
+use std::{fmt::Display, io::Read};
+
+pub use jpeg_marker::JpegMarker;
+
+use jpeg_marker::read_byte;
+
+use super::super::JpegError;
+
+/// Represents a segment of JPEG format: a marker and its data.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct JpegSegment {
+    marker: JpegMarker,
+    data: Vec<u8>,
+    scan_data: Vec<u8>,
+}
+
+impl JpegSegment {
+    pub fn marker(&self) -> &JpegMarker {
+        &self.marker
+    }
+
+    /// Data without the length field. Empty for standalone markers.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Entropy-coded data following SOS segment, as stored
+    /// (with stuffed 0x00 bytes and RSTn markers). Empty for other segments.
+    pub fn scan_data(&self) -> &[u8] {
+        &self.scan_data
+    }
+
+    /// Reads marker of the next segment.
+    pub(super) fn read_marker<R>(r: &mut R) -> Result<JpegMarker, JpegError>
+    where
+        R: Read,
+    {
+        JpegMarker::from_reader(r)
+    }
+
+    /// Reads segment of `marker` whose marker bytes are already read.
+    ///
+    /// # Returns
+    ///
+    /// Segment and, for SOS, the marker ending its entropy-coded data.
+    ///
+    /// # Failures
+    ///
+    /// - `JpegError::TruncatedSegment` when `r` reaches EOF in the middle of the segment.
+    /// - `JpegError::InvalidSegmentLength` when the length field is less than 2.
+    pub(super) fn from_reader<R>(
+        r: &mut R,
+        marker: JpegMarker,
+    ) -> Result<(Self, Option<JpegMarker>), JpegError>
+    where
+        R: Read,
+    {
+        let mut segment = Self {
+            marker,
+            data: vec![],
+            scan_data: vec![],
+        };
+        if marker.is_standalone() {
+            return Ok((segment, None));
+        }
+
+        let len = u16::from_be_bytes([read_byte(r)?, read_byte(r)?]);
+        if len < 2 {
+            return Err(JpegError::InvalidSegmentLength(len));
+        }
+        segment.data = vec![0u8; len as usize - 2];
+        r.read_exact(&mut segment.data)
+            .map_err(JpegError::from_read_error)?;
+
+        if marker == JpegMarker::SOS {
+            let next_marker = Self::read_scan_data(r, &mut segment.scan_data)?;
+            Ok((segment, Some(next_marker)))
+        } else {
+            Ok((segment, None))
+        }
+    }
+
+    /// Reads entropy-coded data up to the next marker other than RSTn.
+    ///
+    /// # Returns
+    ///
+    /// The marker ending the data.
+    fn read_scan_data<R>(r: &mut R, scan_data: &mut Vec<u8>) -> Result<JpegMarker, JpegError>
+    where
+        R: Read,
+    {
+        loop {
+            let byte = read_byte(r)?;
+            if byte != 0xFF {
+                scan_data.push(byte);
+                continue;
+            }
+            let mut next = read_byte(r)?;
+            // fill bytes
+            while next == 0xFF {
+                next = read_byte(r)?;
+            }
+            let marker = JpegMarker::new(next);
+            if next == 0x00 || marker.is_rst() {
+                scan_data.extend_from_slice(&[0xFF, next]);
+            } else {
+                return Ok(marker);
+            }
+        }
+    }
+}
+
+impl Display for JpegSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Marker: {}, Data len: {}", self.marker, self.data.len())?;
+        if !self.scan_data.is_empty() {
+            write!(f, ", Scan data len: {}", self.scan_data.len())?;
+        }
+        Ok(())
+    }
+}
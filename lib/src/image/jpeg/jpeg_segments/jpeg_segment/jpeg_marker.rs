@@ -0,0 +1,102 @@
+use std::{fmt::Display, io::Read};
+
+use super::super::super::JpegError;
+
+/// Second byte of JPEG's markers (following 0xFF).
+/// 0xD8 (SOI), 0xE1 (APP1), for example.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct JpegMarker(u8);
+
+impl JpegMarker {
+    pub const SOF0: Self = Self(0xC0);
+    pub const SOF2: Self = Self(0xC2);
+    pub const DHT: Self = Self(0xC4);
+    pub const SOI: Self = Self(0xD8);
+    pub const EOI: Self = Self(0xD9);
+    pub const SOS: Self = Self(0xDA);
+    pub const DQT: Self = Self(0xDB);
+    pub const DRI: Self = Self(0xDD);
+    pub const APP0: Self = Self(0xE0);
+    pub const APP1: Self = Self(0xE1);
+    pub const COM: Self = Self(0xFE);
+
+    pub fn new(byte: u8) -> Self {
+        Self(byte)
+    }
+
+    pub fn as_u8(&self) -> u8 {
+        self.0
+    }
+
+    /// Whether APPn (0xE0 to 0xEF), application-specific segment such as JFIF or EXIF.
+    pub fn is_app(&self) -> bool {
+        (0xE0..=0xEF).contains(&self.0)
+    }
+
+    /// Whether SOFn, start of frame holding image size and components.
+    pub fn is_sof(&self) -> bool {
+        (0xC0..=0xCF).contains(&self.0) && ![0xC4, 0xC8, 0xCC].contains(&self.0)
+    }
+
+    /// Whether RSTn (0xD0 to 0xD7), restart marker in entropy-coded data.
+    pub fn is_rst(&self) -> bool {
+        (0xD0..=0xD7).contains(&self.0)
+    }
+
+    /// Whether the marker has no length field nor data: SOI, EOI, RSTn and TEM.
+    pub fn is_standalone(&self) -> bool {
+        self.is_rst() || [0x01, 0xD8, 0xD9].contains(&self.0)
+    }
+
+    /// Reads 0xFF (possibly repeated as fill bytes) and the marker byte.
+    ///
+    /// # Failures
+    ///
+    /// - `JpegError::InvalidMarker` when the first byte is not 0xFF or the marker byte is 0x00.
+    /// - `JpegError::TruncatedSegment` when `r` reaches EOF.
+    pub(super) fn from_reader<R>(r: &mut R) -> Result<Self, JpegError>
+    where
+        R: Read,
+    {
+        let first = read_byte(r)?;
+        if first != 0xFF {
+            return Err(JpegError::InvalidMarker(first));
+        }
+        loop {
+            match read_byte(r)? {
+                0xFF => {}
+                0x00 => return Err(JpegError::InvalidMarker(0x00)),
+                byte => return Ok(Self(byte)),
+            }
+        }
+    }
+}
+
+/// Reads a byte without allocating, as entropy-coded data is read byte by byte.
+pub(super) fn read_byte<R>(r: &mut R) -> Result<u8, JpegError>
+where
+    R: Read,
+{
+    let mut byte = [0u8; 1];
+    r.read_exact(&mut byte)
+        .map_err(JpegError::from_read_error)?;
+    Ok(byte[0])
+}
+
+impl Display for JpegMarker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0xC4 => write!(f, "DHT"),
+            0xD8 => write!(f, "SOI"),
+            0xD9 => write!(f, "EOI"),
+            0xDA => write!(f, "SOS"),
+            0xDB => write!(f, "DQT"),
+            0xDD => write!(f, "DRI"),
+            0xFE => write!(f, "COM"),
+            n if self.is_sof() => write!(f, "SOF{}", n - 0xC0),
+            n if self.is_rst() => write!(f, "RST{}", n - 0xD0),
+            n if self.is_app() => write!(f, "APP{}", n - 0xE0),
+            n => write!(f, "0xFF{:02X}", n),
+        }
+    }
+}
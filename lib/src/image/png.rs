@@ -3,11 +3,32 @@
 //! Intended to be used from chapter3's problems.
 
 mod png_analyzer;
+mod png_apng;
+mod png_chunk_data;
 mod png_chunks;
 mod png_creator;
+mod png_decoder;
+mod png_document;
+mod png_encoder;
+mod png_error;
+mod png_filter;
+mod png_info;
+mod png_writer;
 
 pub use png_analyzer::PngAnalyzer;
-pub use png_chunks::{PngChunk, PngChunkType, PngChunks};
+pub use png_apng::{Animation, AnimationFrame, FrameInput};
+pub use png_chunk_data::{
+    Actl, Bkgd, BlendOp, Chrm, ColorType, DisposeOp, Fctl, Gama, Ihdr, InterlaceMethod, Itxt, Phys,
+    PhysUnit, Plte, RenderingIntent, Srgb, Text, Time, Trns, Ztxt, MAX_DECOMPRESSED_TEXT_LEN,
+};
+pub use png_chunks::{PngChunk, PngChunkType, PngChunks, TryPngChunks};
 pub use png_creator::PngCreator;
+pub use png_decoder::{PixelBuffer, PngImage};
+pub use png_document::PngDocument;
+pub use png_encoder::EncodeOptions;
+pub use png_error::PngError;
+pub use png_filter::{FilterStrategy, FilterType};
+pub use png_info::PngInfo;
+pub use png_writer::{IdatWriter, PngWriter};
 
 const PNG_FILE_SIGNATURE: [u8; 8] = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
@@ -1,6 +1,9 @@
-use std::io::Read;
+use std::io::{self, Read};
 
-use super::{PngChunks, PNG_FILE_SIGNATURE};
+use super::{
+    png_apng, png_decoder, Animation, PngChunks, PngError, PngImage, PngInfo, TryPngChunks,
+    PNG_FILE_SIGNATURE,
+};
 
 /// Reads PNG file signature and chunks.
 ///
@@ -16,12 +19,32 @@ use super::{PngChunks, PNG_FILE_SIGNATURE};
 ///     println!("{}", chunk);
 /// }
 /// ```
+///
+/// Broken input is reported through `try_chunks()`:
+///
+/// ```
+/// use lib::image::png::{PngAnalyzer, PngError};
+///
+/// let not_png: &[u8] = b"GIF89a..";
+/// let res = PngAnalyzer::new(not_png).try_chunks();
+/// assert!(matches!(res, Err(PngError::BadSignature)));
+///
+/// // signature followed by a chunk cut in the middle of its data
+/// let truncated: &[u8] = &[
+///     0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A,
+///     0x00, 0x00, 0x00, 0x0D, b'I', b'H', b'D', b'R', 0x00, 0x00,
+/// ];
+/// let mut chunks = PngAnalyzer::new(truncated).try_chunks().unwrap();
+/// assert!(matches!(chunks.next(), Some(Err(PngError::TruncatedChunk))));
+/// assert!(chunks.next().is_none());
+/// ```
 #[derive(Debug)]
 pub struct PngAnalyzer<R>
 where
     R: Read,
 {
     png_reader: R,
+    strict: bool,
 }
 
 impl<R> PngAnalyzer<R>
@@ -29,21 +52,152 @@ where
     R: Read,
 {
     pub fn new(png_reader: R) -> Self {
-        Self { png_reader }
+        Self {
+            png_reader,
+            strict: false,
+        }
+    }
+
+    /// Constructs analyzer which also verifies CRC of each chunk.
+    /// Corrupted chunks are reported as `PngError::CrcMismatch`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::image::png::{PngAnalyzer, PngChunk, PngCreator, PngError};
+    ///
+    /// let mut creator = PngCreator::default();
+    /// creator.add_chunk(PngChunk::new_text_chunk("Comment", "hello").unwrap());
+    /// let mut bin = creator.finalize();
+    ///
+    /// let mut chunks = PngAnalyzer::new_strict(bin.as_slice()).try_chunks().unwrap();
+    /// assert!(chunks.next().unwrap().is_ok());
+    ///
+    /// // flip a bit in the text
+    /// bin[16] ^= 0x01;
+    /// let mut chunks = PngAnalyzer::new_strict(bin.as_slice()).try_chunks().unwrap();
+    /// assert!(matches!(chunks.next(), Some(Err(PngError::CrcMismatch { .. }))));
+    /// ```
+    pub fn new_strict(png_reader: R) -> Self {
+        Self {
+            png_reader,
+            strict: true,
+        }
     }
 
+    /// Iterates chunks until EOF or the first malformed chunk.
+    ///
+    /// Yields nothing when the file signature is wrong.
     pub fn chunks(mut self) -> PngChunks<R> {
-        self.read_signature();
-        PngChunks::new(self.png_reader)
+        match self.read_signature() {
+            Ok(()) if self.strict => PngChunks::new_strict(self.png_reader),
+            Ok(()) => PngChunks::new(self.png_reader),
+            Err(_) => PngChunks::empty(self.png_reader),
+        }
+    }
+
+    /// Iterates chunks, reporting malformed input as `PngError`.
+    ///
+    /// # Failures
+    ///
+    /// `PngError::BadSignature` when leading 8 bytes do not match PNG file signature.
+    pub fn try_chunks(mut self) -> Result<TryPngChunks<R>, PngError> {
+        self.read_signature()?;
+        if self.strict {
+            Ok(TryPngChunks::new_strict(self.png_reader))
+        } else {
+            Ok(TryPngChunks::new(self.png_reader))
+        }
     }
 
-    /// # Panics
+    /// Reads whole file and summarizes its header and common ancillary chunks.
+    ///
+    /// # Failures
+    ///
+    /// See `PngInfo` and `try_chunks()`.
     ///
-    /// - Leading 8 bytes do not match PNG file signature.
-    /// - Got any io::Error internally.
-    fn read_signature(&mut self) {
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::image::png::{ColorType, PhysUnit, PngAnalyzer};
+    ///
+    /// // 1x1 grayscale image with pHYs chunk
+    /// let png: &[u8] = &[
+    ///     0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A,
+    ///     0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52, 0x00, 0x00, 0x00, 0x01,
+    ///     0x00, 0x00, 0x00, 0x01, 0x08, 0x00, 0x00, 0x00, 0x00, 0x3A, 0x7E, 0x9B, 0x55,
+    ///     0x00, 0x00, 0x00, 0x09, 0x70, 0x48, 0x59, 0x73, 0x00, 0x00, 0x0B, 0x13,
+    ///     0x00, 0x00, 0x0B, 0x13, 0x01, 0x00, 0x9A, 0x9C, 0x18,
+    ///     0x00, 0x00, 0x00, 0x0A, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0xA8,
+    ///     0x07, 0x00, 0x00, 0x81, 0x00, 0x80, 0xD3, 0x94, 0x53, 0x4A,
+    ///     0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+    /// ];
+    ///
+    /// let info = PngAnalyzer::new(png).info().unwrap();
+    /// assert_eq!((info.ihdr.width, info.ihdr.height), (1, 1));
+    /// assert_eq!(info.ihdr.color_type, ColorType::Grayscale);
+    /// let phys = info.phys.unwrap();
+    /// assert_eq!((phys.pixels_per_unit_x, phys.unit), (2835, PhysUnit::Meter));
+    /// assert!(info.gama.is_none());
+    /// ```
+    pub fn info(self) -> Result<PngInfo, PngError> {
+        PngInfo::from_chunks(self.try_chunks()?)
+    }
+
+    /// Decodes image data into RGBA pixels.
+    ///
+    /// Handles all color types, bit depths, filter types and Adam7 interlacing.
+    ///
+    /// # Failures
+    ///
+    /// - `PngError::MissingChunk` when IHDR, PLTE (for indexed-color images) or IDAT is absent.
+    /// - `PngError::InvalidImageData` when IDAT cannot be decompressed or unfiltered.
+    /// - Errors from `try_chunks()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::image::png::{PixelBuffer, PngAnalyzer};
+    ///
+    /// // 1x1 grayscale image whose only pixel is 0x7F
+    /// let png: &[u8] = &[
+    ///     0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A,
+    ///     0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52, 0x00, 0x00, 0x00, 0x01,
+    ///     0x00, 0x00, 0x00, 0x01, 0x08, 0x00, 0x00, 0x00, 0x00, 0x3A, 0x7E, 0x9B, 0x55,
+    ///     0x00, 0x00, 0x00, 0x0A, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0xA8,
+    ///     0x07, 0x00, 0x00, 0x81, 0x00, 0x80, 0xD3, 0x94, 0x53, 0x4A,
+    ///     0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+    /// ];
+    ///
+    /// let image = PngAnalyzer::new(png).decode().unwrap();
+    /// assert_eq!((image.width, image.height), (1, 1));
+    /// assert_eq!(image.pixels, PixelBuffer::Rgba8(vec![0x7F, 0x7F, 0x7F, 0xFF]));
+    /// ```
+    pub fn decode(self) -> Result<PngImage, PngError> {
+        png_decoder::decode(self.try_chunks()?)
+    }
+
+    /// Reads frames of APNG, validating their sequence numbers and regions.
+    ///
+    /// # Failures
+    ///
+    /// - `PngError::MissingChunk` when IHDR or acTL is absent.
+    /// - `PngError::InvalidAnimation` when sequence numbers are not in order, the number of frames
+    ///   differs from acTL, or any frame region exceeds the canvas.
+    /// - Errors from `try_chunks()`.
+    ///
+    /// See `PngCreator::from_frames()` for examples.
+    pub fn animation(self) -> Result<Animation, PngError> {
+        png_apng::animation(self.try_chunks()?)
+    }
+
+    fn read_signature(&mut self) -> Result<(), PngError> {
         let mut sig = [0u8; 8];
-        self.png_reader.read_exact(&mut sig).unwrap();
-        assert_eq!(sig, PNG_FILE_SIGNATURE, "wrong PNG file signature");
+        match self.png_reader.read_exact(&mut sig) {
+            Ok(()) if sig == PNG_FILE_SIGNATURE => Ok(()),
+            Ok(()) => Err(PngError::BadSignature),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Err(PngError::BadSignature),
+            Err(e) => Err(e.into()),
+        }
     }
 }
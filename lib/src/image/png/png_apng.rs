@@ -0,0 +1,210 @@
+use std::convert::TryFrom;
+
+use super::{
+    png_encoder::{self, EncodeOptions},
+    Actl, Fctl, Ihdr, PngChunk, PngChunkType, PngError,
+};
+
+/// Animation of an APNG file.
+///
+/// Made by `PngAnalyzer::animation()`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Animation {
+    pub ihdr: Ihdr,
+    pub actl: Actl,
+    /// Frames in display order.
+    pub frames: Vec<AnimationFrame>,
+    /// Whether the default image (IDAT) is the first frame.
+    /// Otherwise the default image is shown only by decoders without APNG support.
+    pub default_image_is_first_frame: bool,
+}
+
+/// Frame of APNG.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct AnimationFrame {
+    /// Region, delay, dispose and blend ops of the frame.
+    pub fctl: Fctl,
+    /// zlib stream of filtered scanlines, concatenated from IDAT or fdAT chunks
+    /// (sequence numbers of fdAT are removed).
+    pub data: Vec<u8>,
+}
+
+/// Frame passed to `PngCreator::from_frames()`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct FrameInput<'p> {
+    /// Region, delay, dispose and blend ops of the frame.
+    /// `sequence_number` is ignored and assigned while encoding.
+    pub fctl: Fctl,
+    /// Scanlines of the frame region in the same layout as `PngCreator::from_pixels()`.
+    pub pixels: &'p [u8],
+}
+
+/// Reads animation from chunks in file order, validating sequence numbers and frame regions.
+pub(super) fn animation<I>(chunks: I) -> Result<Animation, PngError>
+where
+    I: Iterator<Item = Result<PngChunk, PngError>>,
+{
+    let err = |reason| Err(PngError::InvalidAnimation(reason));
+
+    let mut ihdr = None;
+    let mut actl = None;
+    let mut frames = Vec::<AnimationFrame>::new();
+    let mut default_image_is_first_frame = false;
+    let mut next_sequence_number = 0u32;
+    let mut seen_idat = false;
+
+    for chunk in chunks {
+        let chunk = chunk?;
+        let typ = chunk.typ();
+        if ihdr.is_none() {
+            if typ != &PngChunkType::IHDR {
+                return Err(PngError::MissingChunk(PngChunkType::IHDR));
+            }
+            ihdr = Some(Ihdr::from_chunk(&chunk)?);
+        } else if typ == &PngChunkType::ACTL {
+            if seen_idat {
+                return Err(PngError::InvalidChunkOrder {
+                    typ: typ.clone(),
+                    reason: "must come before IDAT",
+                });
+            }
+            actl = Some(Actl::from_chunk(&chunk)?);
+        } else if typ == &PngChunkType::FCTL {
+            let fctl = Fctl::from_chunk(&chunk)?;
+            if fctl.sequence_number != next_sequence_number {
+                return err("sequence numbers are not in order");
+            }
+            next_sequence_number = next_sequence_number.wrapping_add(1);
+            if frames.is_empty() && !seen_idat {
+                default_image_is_first_frame = true;
+            }
+            frames.push(AnimationFrame { fctl, data: vec![] });
+        } else if typ == &PngChunkType::IDAT {
+            seen_idat = true;
+            if default_image_is_first_frame {
+                if let Some(frame) = frames.last_mut() {
+                    frame.data.extend_from_slice(chunk.data());
+                }
+            }
+        } else if typ == &PngChunkType::FDAT {
+            let (sequence_number, data) = match chunk.data() {
+                [a, b, c, d, data @ ..] => (u32::from_be_bytes([*a, *b, *c, *d]), data),
+                _ => return err("fdAT without sequence number"),
+            };
+            if sequence_number != next_sequence_number {
+                return err("sequence numbers are not in order");
+            }
+            next_sequence_number = next_sequence_number.wrapping_add(1);
+            match frames.last_mut() {
+                Some(frame) if seen_idat => frame.data.extend_from_slice(data),
+                _ => return err("fdAT must follow fcTL after IDAT"),
+            }
+        } else if typ == &PngChunkType::IEND {
+            break;
+        }
+    }
+
+    let ihdr = ihdr.ok_or(PngError::MissingChunk(PngChunkType::IHDR))?;
+    let actl = actl.ok_or(PngError::MissingChunk(PngChunkType::ACTL))?;
+    if u32::try_from(frames.len()) != Ok(actl.num_frames) {
+        return err("number of frames differs from acTL");
+    }
+    validate_regions(&ihdr, frames.iter().map(|f| &f.fctl))?;
+    if frames.iter().any(|f| f.data.is_empty()) {
+        return err("frame has no image data");
+    }
+
+    Ok(Animation {
+        ihdr,
+        actl,
+        frames,
+        default_image_is_first_frame,
+    })
+}
+
+/// Encodes frames into chunks of APNG, whose default image is the first frame.
+pub(super) fn encode(
+    ihdr: &Ihdr,
+    frames: &[FrameInput],
+    num_plays: u32,
+    options: &EncodeOptions,
+) -> Result<Vec<PngChunk>, PngError> {
+    let num_frames =
+        u32::try_from(frames.len()).map_err(|_| PngError::InvalidAnimation("too many frames"))?;
+    if num_frames == 0 {
+        return Err(PngError::InvalidAnimation("no frames"));
+    }
+
+    validate_regions(ihdr, frames.iter().map(|f| &f.fctl))?;
+
+    let mut chunks = vec![ihdr.to_chunk()?];
+    chunks.push(
+        Actl {
+            num_frames,
+            num_plays,
+        }
+        .to_chunk(),
+    );
+    chunks.extend(png_encoder::palette_chunk(ihdr, options)?);
+
+    let mut sequence_number = 0u32;
+    for (i, frame) in frames.iter().enumerate() {
+        let fctl = Fctl {
+            sequence_number,
+            ..frame.fctl.clone()
+        };
+        sequence_number += 1;
+        let frame_ihdr = Ihdr {
+            width: fctl.width,
+            height: fctl.height,
+            ..ihdr.clone()
+        };
+        let compressed = png_encoder::compress(&frame_ihdr, frame.pixels, options)?;
+        chunks.push(fctl.to_chunk());
+
+        for data in png_encoder::split(&compressed, options) {
+            if i == 0 {
+                chunks.push(PngChunk::with_crc(PngChunkType::IDAT, data.to_vec()));
+            } else {
+                let mut fdat = sequence_number.to_be_bytes().to_vec();
+                fdat.extend_from_slice(data);
+                chunks.push(PngChunk::with_crc(PngChunkType::FDAT, fdat));
+                sequence_number += 1;
+            }
+        }
+    }
+    chunks.push(PngChunk::with_crc(PngChunkType::IEND, vec![]));
+
+    Ok(chunks)
+}
+
+/// Checks all frames are non-empty and fit in the canvas, and the first one covers the whole.
+fn validate_regions<'f, I>(ihdr: &Ihdr, fctls: I) -> Result<(), PngError>
+where
+    I: Iterator<Item = &'f Fctl>,
+{
+    let fits = |offset: u32, len: u32, canvas: u32| {
+        offset.checked_add(len).is_some_and(|end| end <= canvas)
+    };
+    for (i, fctl) in fctls.enumerate() {
+        if fctl.width == 0 || fctl.height == 0 {
+            return Err(PngError::InvalidAnimation("frame must not be empty"));
+        }
+        if !fits(fctl.x_offset, fctl.width, ihdr.width)
+            || !fits(fctl.y_offset, fctl.height, ihdr.height)
+        {
+            return Err(PngError::InvalidAnimation(
+                "frame region exceeds the canvas",
+            ));
+        }
+        if i == 0
+            && (fctl.x_offset, fctl.y_offset, fctl.width, fctl.height)
+                != (0, 0, ihdr.width, ihdr.height)
+        {
+            return Err(PngError::InvalidAnimation(
+                "first frame must cover the whole canvas",
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,144 @@
+//! Typed views of chunk data.
+//!
+//! Each type is decoded from a `PngChunk` by its `from_chunk()`.
+
+mod actl;
+mod bkgd;
+mod chrm;
+mod fctl;
+mod gama;
+mod ihdr;
+mod itxt;
+mod phys;
+mod plte;
+mod srgb;
+mod text;
+mod time;
+mod trns;
+mod ztxt;
+
+pub use actl::Actl;
+pub use bkgd::Bkgd;
+pub use chrm::Chrm;
+pub use fctl::{BlendOp, DisposeOp, Fctl};
+pub use gama::Gama;
+pub use ihdr::{ColorType, Ihdr, InterlaceMethod};
+pub use itxt::Itxt;
+pub use phys::{Phys, PhysUnit};
+pub use plte::Plte;
+pub use srgb::{RenderingIntent, Srgb};
+pub use text::Text;
+pub use time::Time;
+pub use trns::Trns;
+pub use ztxt::Ztxt;
+
+use std::{
+    convert::TryFrom,
+    io::{Read, Write},
+};
+
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+
+use super::{PngChunk, PngChunkType, PngError};
+
+/// Maximum length of text decompressed from a zTXt or iTXt chunk (8 MiB, same as libpng's default).
+/// Longer text is rejected so that a small chunk cannot expand to exhaust memory.
+pub const MAX_DECOMPRESSED_TEXT_LEN: usize = 8 * 1024 * 1024;
+
+/// Checks that `chunk` has type `typ` and returns its data.
+fn data_of<'c>(chunk: &'c PngChunk, typ: &PngChunkType) -> Result<&'c [u8], PngError> {
+    if chunk.typ() == typ {
+        Ok(chunk.data())
+    } else {
+        Err(invalid(typ, "unexpected chunk type"))
+    }
+}
+
+/// Checks that `chunk` has type `typ` and its data is `len` bytes long.
+fn fixed_len_data_of<'c>(
+    chunk: &'c PngChunk,
+    typ: &PngChunkType,
+    len: usize,
+) -> Result<&'c [u8], PngError> {
+    let data = data_of(chunk, typ)?;
+    if data.len() == len {
+        Ok(data)
+    } else {
+        Err(invalid(typ, "unexpected data length"))
+    }
+}
+
+fn invalid(typ: &PngChunkType, reason: &'static str) -> PngError {
+    PngError::InvalidChunkData {
+        typ: typ.clone(),
+        reason,
+    }
+}
+
+/// Latin-1 code points are equal to the first 256 of Unicode.
+fn latin1_to_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| char::from(b)).collect()
+}
+
+/// None when `s` has characters out of Latin-1.
+fn string_to_latin1(s: &str) -> Option<Vec<u8>> {
+    s.chars().map(|c| u8::try_from(c).ok()).collect()
+}
+
+/// Encodes text of tEXt or zTXt chunk, which must be Latin-1 without null characters.
+fn text_to_latin1(text: &str, typ: &PngChunkType) -> Result<Vec<u8>, PngError> {
+    if text.contains('\0') {
+        return Err(invalid(typ, "text contains a null character"));
+    }
+    string_to_latin1(text).ok_or_else(|| invalid(typ, "text has characters out of Latin-1"))
+}
+
+/// Keyword is 1-79 printable Latin-1 characters
+/// without leading, trailing or consecutive spaces.
+fn validate_keyword(keyword: &str, typ: &PngChunkType) -> Result<(), PngError> {
+    let printable = keyword
+        .chars()
+        .all(|c| (' '..='~').contains(&c) || ('\u{A1}'..='\u{FF}').contains(&c));
+    if !printable
+        || !(1..=79).contains(&keyword.chars().count())
+        || keyword.starts_with(' ')
+        || keyword.ends_with(' ')
+        || keyword.contains("  ")
+    {
+        Err(invalid(typ, "invalid keyword"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Splits data of textual chunks into keyword and the rest after null separator.
+fn split_keyword<'d>(data: &'d [u8], typ: &PngChunkType) -> Result<(String, &'d [u8]), PngError> {
+    let nul = data
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or_else(|| invalid(typ, "missing null separator"))?;
+    let keyword = latin1_to_string(&data[..nul]);
+    validate_keyword(&keyword, typ)?;
+    Ok((keyword, &data[nul + 1..]))
+}
+
+fn compress(data: &[u8]) -> Result<Vec<u8>, PngError> {
+    let mut encoder = ZlibEncoder::new(Vec::<u8>::new(), Compression::default());
+    encoder.write_all(data)?;
+    Ok(encoder.finish()?)
+}
+
+/// Decompresses text of zTXt or iTXt chunk, up to `MAX_DECOMPRESSED_TEXT_LEN` bytes.
+fn decompress(data: &[u8], typ: &PngChunkType) -> Result<Vec<u8>, PngError> {
+    let mut buf = Vec::<u8>::new();
+    // Reading 1 extra byte to detect text over the limit.
+    ZlibDecoder::new(data)
+        .take(MAX_DECOMPRESSED_TEXT_LEN as u64 + 1)
+        .read_to_end(&mut buf)
+        .map_err(|_| invalid(typ, "corrupt zlib stream"))?;
+    if buf.len() > MAX_DECOMPRESSED_TEXT_LEN {
+        Err(invalid(typ, "decompressed text is too long"))
+    } else {
+        Ok(buf)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::binary::{BinaryRead, BinaryWrite};
+
+use super::{fixed_len_data_of, invalid, PngChunk, PngChunkType, PngError};
+
+/// Animation control of APNG ("acTL" chunk).
+#[derive(BinaryRead, BinaryWrite, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Actl {
+    /// Number of frames, at least 1.
+    pub num_frames: u32,
+    /// Number of times to loop the animation. 0 means infinitely.
+    pub num_plays: u32,
+}
+
+impl Actl {
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when `chunk` is not a valid acTL chunk.
+    pub fn from_chunk(chunk: &PngChunk) -> Result<Self, PngError> {
+        let typ = PngChunkType::ACTL;
+        let mut data = fixed_len_data_of(chunk, &typ, 8)?;
+        let actl = Self::read_from(&mut data)?;
+        if actl.num_frames == 0 {
+            return Err(invalid(&typ, "number of frames must not be 0"));
+        }
+        Ok(actl)
+    }
+
+    pub fn to_chunk(&self) -> PngChunk {
+        let mut data = Vec::with_capacity(8);
+        self.write_to(&mut data)
+            .expect("writing to Vec never fails");
+        PngChunk::with_crc(PngChunkType::ACTL, data)
+    }
+}
@@ -0,0 +1,46 @@
+use crate::binary::{self, Endian};
+
+use super::{fixed_len_data_of, ColorType, PngChunk, PngChunkType, PngError};
+
+/// Background color ("bKGD" chunk).
+/// Layout depends on the color type of the image.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Bkgd {
+    /// For grayscale images, with or without alpha.
+    Grayscale(u16),
+    /// For RGB images, with or without alpha.
+    Rgb(u16, u16, u16),
+    /// For indexed-color images.
+    PaletteIndex(u8),
+}
+
+impl Bkgd {
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when `chunk` is not a valid bKGD chunk
+    /// for an image of `color_type`.
+    pub fn from_chunk(chunk: &PngChunk, color_type: ColorType) -> Result<Self, PngError> {
+        let typ = PngChunkType::BKGD;
+        match color_type {
+            ColorType::Grayscale | ColorType::GrayscaleAlpha => {
+                let mut data = fixed_len_data_of(chunk, &typ, 2)?;
+                Ok(Bkgd::Grayscale(binary::read(
+                    &mut data,
+                    &Endian::BigEndian,
+                )?))
+            }
+            ColorType::Rgb | ColorType::Rgba => {
+                let mut data = fixed_len_data_of(chunk, &typ, 6)?;
+                Ok(Bkgd::Rgb(
+                    binary::read(&mut data, &Endian::BigEndian)?,
+                    binary::read(&mut data, &Endian::BigEndian)?,
+                    binary::read(&mut data, &Endian::BigEndian)?,
+                ))
+            }
+            ColorType::Indexed => {
+                let data = fixed_len_data_of(chunk, &typ, 1)?;
+                Ok(Bkgd::PaletteIndex(data[0]))
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::binary::{self, Endian};
+
+use super::{fixed_len_data_of, PngChunk, PngChunkType, PngError};
+
+/// Primary chromaticities and white point ("cHRM" chunk).
+///
+/// Each value is a CIE 1931 `(x, y)` pair times 100000.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Chrm {
+    pub white_point: (u32, u32),
+    pub red: (u32, u32),
+    pub green: (u32, u32),
+    pub blue: (u32, u32),
+}
+
+impl Chrm {
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when `chunk` is not a valid cHRM chunk.
+    pub fn from_chunk(chunk: &PngChunk) -> Result<Self, PngError> {
+        let mut data = fixed_len_data_of(chunk, &PngChunkType::CHRM, 32)?;
+        let mut read_xy = || -> Result<(u32, u32), PngError> {
+            Ok((
+                binary::read(&mut data, &Endian::BigEndian)?,
+                binary::read(&mut data, &Endian::BigEndian)?,
+            ))
+        };
+        Ok(Self {
+            white_point: read_xy()?,
+            red: read_xy()?,
+            green: read_xy()?,
+            blue: read_xy()?,
+        })
+    }
+}
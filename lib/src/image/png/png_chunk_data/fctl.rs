@@ -0,0 +1,110 @@
+use crate::binary::{self, Endian};
+
+use super::{fixed_len_data_of, invalid, PngChunk, PngChunkType, PngError};
+
+/// Frame control of APNG ("fcTL" chunk).
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Fctl {
+    /// Position among fcTL and fdAT chunks, starting from 0.
+    pub sequence_number: u32,
+    pub width: u32,
+    pub height: u32,
+    pub x_offset: u32,
+    pub y_offset: u32,
+    /// Numerator of frame delay in seconds.
+    pub delay_num: u16,
+    /// Denominator of frame delay in seconds. 0 is treated as 100.
+    pub delay_den: u16,
+    pub dispose_op: DisposeOp,
+    pub blend_op: BlendOp,
+}
+
+impl Fctl {
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when `chunk` is not a valid fcTL chunk.
+    pub fn from_chunk(chunk: &PngChunk) -> Result<Self, PngError> {
+        let typ = PngChunkType::FCTL;
+        let mut data = fixed_len_data_of(chunk, &typ, 26)?;
+        let sequence_number = binary::read(&mut data, &Endian::BigEndian)?;
+        let width = binary::read(&mut data, &Endian::BigEndian)?;
+        let height = binary::read(&mut data, &Endian::BigEndian)?;
+        let x_offset = binary::read(&mut data, &Endian::BigEndian)?;
+        let y_offset = binary::read(&mut data, &Endian::BigEndian)?;
+        let delay_num = binary::read(&mut data, &Endian::BigEndian)?;
+        let delay_den = binary::read(&mut data, &Endian::BigEndian)?;
+        let dispose_op = match data[0] {
+            0 => DisposeOp::None,
+            1 => DisposeOp::Background,
+            2 => DisposeOp::Previous,
+            _ => return Err(invalid(&typ, "unknown dispose op")),
+        };
+        let blend_op = match data[1] {
+            0 => BlendOp::Source,
+            1 => BlendOp::Over,
+            _ => return Err(invalid(&typ, "unknown blend op")),
+        };
+        if width == 0 || height == 0 {
+            return Err(invalid(&typ, "frame must not be empty"));
+        }
+        Ok(Self {
+            sequence_number,
+            width,
+            height,
+            x_offset,
+            y_offset,
+            delay_num,
+            delay_den,
+            dispose_op,
+            blend_op,
+        })
+    }
+
+    pub fn to_chunk(&self) -> PngChunk {
+        let mut data = Vec::<u8>::with_capacity(26);
+        for n in &[
+            self.sequence_number,
+            self.width,
+            self.height,
+            self.x_offset,
+            self.y_offset,
+        ] {
+            data.extend_from_slice(&n.to_be_bytes());
+        }
+        data.extend_from_slice(&self.delay_num.to_be_bytes());
+        data.extend_from_slice(&self.delay_den.to_be_bytes());
+        data.push(self.dispose_op as u8);
+        data.push(self.blend_op as u8);
+        PngChunk::with_crc(PngChunkType::FCTL, data)
+    }
+
+    /// Frame delay in seconds.
+    pub fn delay(&self) -> f64 {
+        let den = if self.delay_den == 0 {
+            100
+        } else {
+            self.delay_den
+        };
+        self.delay_num as f64 / den as f64
+    }
+}
+
+/// How the frame region is disposed before rendering the next frame.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum DisposeOp {
+    /// Left as it is.
+    None = 0,
+    /// Cleared to fully transparent black.
+    Background = 1,
+    /// Reverted to the previous contents.
+    Previous = 2,
+}
+
+/// How the frame is rendered onto the output buffer.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum BlendOp {
+    /// Overwrites the region, including alpha.
+    Source = 0,
+    /// Alpha-composites over the region.
+    Over = 1,
+}
@@ -0,0 +1,26 @@
+use crate::binary::{self, Endian};
+
+use super::{fixed_len_data_of, PngChunk, PngChunkType, PngError};
+
+/// Image gamma ("gAMA" chunk).
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Gama {
+    /// Gamma times 100000.
+    pub gamma: u32,
+}
+
+impl Gama {
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when `chunk` is not a valid gAMA chunk.
+    pub fn from_chunk(chunk: &PngChunk) -> Result<Self, PngError> {
+        let mut data = fixed_len_data_of(chunk, &PngChunkType::GAMA, 4)?;
+        let gamma = binary::read(&mut data, &Endian::BigEndian)?;
+        Ok(Self { gamma })
+    }
+
+    /// Gamma as a floating point number (0.45455 for sRGB, for example).
+    pub fn value(&self) -> f64 {
+        self.gamma as f64 / 100_000.0
+    }
+}
@@ -0,0 +1,170 @@
+use crate::binary::{self, Endian};
+
+use super::{fixed_len_data_of, invalid, PngChunk, PngChunkType, PngError};
+
+/// Image header ("IHDR" chunk).
+///
+/// # Examples
+///
+/// ```
+/// use lib::image::png::{ColorType, Ihdr, InterlaceMethod, PngAnalyzer};
+///
+/// let png: &[u8] = &[
+///     0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, // signature
+///     0x00, 0x00, 0x00, 0x0D, b'I', b'H', b'D', b'R',
+///     0x00, 0x00, 0x01, 0x00, // width
+///     0x00, 0x00, 0x00, 0x80, // height
+///     0x08, 0x06, 0x00, 0x00, 0x00, // bit depth, color type, compression, filter, interlace
+///     0xE4, 0xB5, 0xB7, 0x0A, // CRC
+/// ];
+/// let chunk = PngAnalyzer::new(png).chunks().next().unwrap();
+/// let ihdr = Ihdr::from_chunk(&chunk).unwrap();
+/// assert_eq!((ihdr.width, ihdr.height), (256, 128));
+/// assert_eq!(ihdr.color_type, ColorType::Rgba);
+/// assert_eq!(ihdr.bit_depth, 8);
+/// assert_eq!(ihdr.interlace_method, InterlaceMethod::None);
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Ihdr {
+    pub width: u32,
+    pub height: u32,
+    pub bit_depth: u8,
+    pub color_type: ColorType,
+    pub interlace_method: InterlaceMethod,
+}
+
+impl Ihdr {
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when `chunk` is not a valid IHDR chunk.
+    pub fn from_chunk(chunk: &PngChunk) -> Result<Self, PngError> {
+        let typ = PngChunkType::IHDR;
+        let mut data = fixed_len_data_of(chunk, &typ, 13)?;
+
+        let width: u32 = binary::read(&mut data, &Endian::BigEndian)?;
+        let height: u32 = binary::read(&mut data, &Endian::BigEndian)?;
+        let [bit_depth, color_type, compression_method, filter_method, interlace_method] = {
+            let mut buf = [0u8; 5];
+            buf.copy_from_slice(data);
+            buf
+        };
+
+        let color_type =
+            ColorType::from_u8(color_type).ok_or_else(|| invalid(&typ, "unknown color type"))?;
+        if compression_method != 0 {
+            return Err(invalid(&typ, "unknown compression method"));
+        }
+        if filter_method != 0 {
+            return Err(invalid(&typ, "unknown filter method"));
+        }
+        let interlace_method = match interlace_method {
+            0 => InterlaceMethod::None,
+            1 => InterlaceMethod::Adam7,
+            _ => return Err(invalid(&typ, "unknown interlace method")),
+        };
+
+        let ihdr = Self {
+            width,
+            height,
+            bit_depth,
+            color_type,
+            interlace_method,
+        };
+        ihdr.validate()?;
+        Ok(ihdr)
+    }
+
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when dimensions or bit depth are not allowed.
+    pub fn to_chunk(&self) -> Result<PngChunk, PngError> {
+        self.validate()?;
+        let mut data = Vec::<u8>::with_capacity(13);
+        data.extend_from_slice(&self.width.to_be_bytes());
+        data.extend_from_slice(&self.height.to_be_bytes());
+        data.push(self.bit_depth);
+        data.push(self.color_type.as_u8());
+        data.push(0); // compression method
+        data.push(0); // filter method
+        data.push(match self.interlace_method {
+            InterlaceMethod::None => 0,
+            InterlaceMethod::Adam7 => 1,
+        });
+        Ok(PngChunk::with_crc(PngChunkType::IHDR, data))
+    }
+
+    fn validate(&self) -> Result<(), PngError> {
+        let typ = PngChunkType::IHDR;
+        let dimension_range = 1..=0x7FFF_FFFF;
+        if !dimension_range.contains(&self.width) || !dimension_range.contains(&self.height) {
+            return Err(invalid(&typ, "image dimensions out of range"));
+        }
+        if !self
+            .color_type
+            .allowed_bit_depths()
+            .contains(&self.bit_depth)
+        {
+            return Err(invalid(&typ, "bit depth not allowed for the color type"));
+        }
+        Ok(())
+    }
+}
+
+/// Color type in IHDR chunk.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum ColorType {
+    Grayscale,
+    Rgb,
+    Indexed,
+    GrayscaleAlpha,
+    Rgba,
+}
+
+impl ColorType {
+    /// Number of samples per pixel.
+    pub fn channels(&self) -> usize {
+        match self {
+            ColorType::Grayscale | ColorType::Indexed => 1,
+            ColorType::GrayscaleAlpha => 2,
+            ColorType::Rgb => 3,
+            ColorType::Rgba => 4,
+        }
+    }
+
+    /// Bit depths the specification allows for this color type.
+    pub fn allowed_bit_depths(&self) -> &'static [u8] {
+        match self {
+            ColorType::Grayscale => &[1, 2, 4, 8, 16],
+            ColorType::Indexed => &[1, 2, 4, 8],
+            ColorType::Rgb | ColorType::GrayscaleAlpha | ColorType::Rgba => &[8, 16],
+        }
+    }
+
+    pub fn from_u8(n: u8) -> Option<Self> {
+        match n {
+            0 => Some(ColorType::Grayscale),
+            2 => Some(ColorType::Rgb),
+            3 => Some(ColorType::Indexed),
+            4 => Some(ColorType::GrayscaleAlpha),
+            6 => Some(ColorType::Rgba),
+            _ => None,
+        }
+    }
+
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            ColorType::Grayscale => 0,
+            ColorType::Rgb => 2,
+            ColorType::Indexed => 3,
+            ColorType::GrayscaleAlpha => 4,
+            ColorType::Rgba => 6,
+        }
+    }
+}
+
+/// Interlace method in IHDR chunk.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum InterlaceMethod {
+    None,
+    Adam7,
+}
@@ -0,0 +1,111 @@
+use std::str;
+
+use super::{
+    compress, data_of, decompress, invalid, split_keyword, string_to_latin1, validate_keyword,
+    PngChunk, PngChunkType, PngError,
+};
+
+/// International UTF-8 text, optionally compressed ("iTXt" chunk).
+///
+/// # Examples
+///
+/// ```
+/// use lib::image::png::{Itxt, PngChunk};
+///
+/// let chunk = PngChunk::new_itxt_chunk("Title", "ja", "タイトル", "Go ならわかるシステムプログラミング", true)
+///     .unwrap();
+///
+/// let itxt = Itxt::from_chunk(&chunk).unwrap();
+/// assert_eq!(itxt.keyword, "Title");
+/// assert_eq!(itxt.language_tag, "ja");
+/// assert_eq!(itxt.translated_keyword, "タイトル");
+/// assert_eq!(itxt.text, "Go ならわかるシステムプログラミング");
+/// assert!(itxt.compressed);
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Itxt {
+    pub keyword: String,
+    /// Whether text is stored compressed.
+    pub compressed: bool,
+    /// RFC 3066 language tag such as "en-US". Empty when unknown.
+    pub language_tag: String,
+    /// Keyword translated into the language. Empty when absent.
+    pub translated_keyword: String,
+    pub text: String,
+}
+
+impl Itxt {
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when `chunk` is not a valid iTXt chunk
+    /// or its compressed text expands to more than `MAX_DECOMPRESSED_TEXT_LEN` bytes.
+    pub fn from_chunk(chunk: &PngChunk) -> Result<Self, PngError> {
+        let typ = PngChunkType::ITXT;
+        let (keyword, rest) = split_keyword(data_of(chunk, &typ)?, &typ)?;
+
+        let (compressed, rest) = match rest {
+            [0, _, rest @ ..] => (false, rest),
+            [1, 0, rest @ ..] => (true, rest),
+            [1, _, ..] => return Err(invalid(&typ, "unknown compression method")),
+            [_, _, ..] => return Err(invalid(&typ, "unknown compression flag")),
+            _ => return Err(invalid(&typ, "missing compression flag")),
+        };
+        let mut fields = rest.splitn(3, |&b| b == 0);
+        let (language_tag, translated_keyword, text) =
+            match (fields.next(), fields.next(), fields.next()) {
+                (Some(lang), Some(translated), Some(text)) => (lang, translated, text),
+                _ => return Err(invalid(&typ, "missing null separator")),
+            };
+
+        let language_tag = str::from_utf8(language_tag)
+            .ok()
+            .filter(|lang| lang.is_ascii())
+            .ok_or_else(|| invalid(&typ, "language tag is not ASCII"))?;
+        let to_utf8 = |bytes: Vec<u8>| {
+            String::from_utf8(bytes).map_err(|_| invalid(&typ, "text is not UTF-8"))
+        };
+        let text = if compressed {
+            decompress(text, &typ)?
+        } else {
+            text.to_vec()
+        };
+
+        Ok(Self {
+            keyword,
+            compressed,
+            language_tag: language_tag.to_string(),
+            translated_keyword: to_utf8(translated_keyword.to_vec())?,
+            text: to_utf8(text)?,
+        })
+    }
+
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when keyword is invalid or
+    /// language tag is not ASCII or any field other than text contains a null character.
+    pub fn to_chunk(&self) -> Result<PngChunk, PngError> {
+        let typ = PngChunkType::ITXT;
+        validate_keyword(&self.keyword, &typ)?;
+        if !self.language_tag.is_ascii() || self.language_tag.contains('\0') {
+            return Err(invalid(&typ, "language tag must be ASCII without null"));
+        }
+        if self.translated_keyword.contains('\0') {
+            return Err(invalid(&typ, "translated keyword must not contain null"));
+        }
+
+        let mut data = string_to_latin1(&self.keyword).unwrap_or_default();
+        data.push(0);
+        data.push(self.compressed as u8);
+        data.push(0); // compression method: zlib
+        data.extend_from_slice(self.language_tag.as_bytes());
+        data.push(0);
+        data.extend_from_slice(self.translated_keyword.as_bytes());
+        data.push(0);
+        if self.compressed {
+            data.extend_from_slice(&compress(self.text.as_bytes())?);
+        } else {
+            data.extend_from_slice(self.text.as_bytes());
+        }
+        PngChunk::new(typ, data)
+    }
+}
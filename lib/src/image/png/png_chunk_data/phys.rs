@@ -0,0 +1,41 @@
+use crate::binary::{self, Endian};
+
+use super::{fixed_len_data_of, invalid, PngChunk, PngChunkType, PngError};
+
+/// Physical pixel dimensions ("pHYs" chunk).
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Phys {
+    pub pixels_per_unit_x: u32,
+    pub pixels_per_unit_y: u32,
+    pub unit: PhysUnit,
+}
+
+impl Phys {
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when `chunk` is not a valid pHYs chunk.
+    pub fn from_chunk(chunk: &PngChunk) -> Result<Self, PngError> {
+        let typ = PngChunkType::PHYS;
+        let mut data = fixed_len_data_of(chunk, &typ, 9)?;
+        let pixels_per_unit_x = binary::read(&mut data, &Endian::BigEndian)?;
+        let pixels_per_unit_y = binary::read(&mut data, &Endian::BigEndian)?;
+        let unit = match data[0] {
+            0 => PhysUnit::Unknown,
+            1 => PhysUnit::Meter,
+            _ => return Err(invalid(&typ, "unknown unit specifier")),
+        };
+        Ok(Self {
+            pixels_per_unit_x,
+            pixels_per_unit_y,
+            unit,
+        })
+    }
+}
+
+/// Unit specifier in pHYs chunk.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum PhysUnit {
+    /// Only the aspect ratio is defined.
+    Unknown,
+    Meter,
+}
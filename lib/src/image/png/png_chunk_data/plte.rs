@@ -0,0 +1,38 @@
+use super::{data_of, invalid, PngChunk, PngChunkType, PngError};
+
+/// Palette ("PLTE" chunk).
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Plte {
+    /// RGB entries, 1 to 256.
+    pub entries: Vec<[u8; 3]>,
+}
+
+impl Plte {
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when `chunk` is not a valid PLTE chunk.
+    pub fn from_chunk(chunk: &PngChunk) -> Result<Self, PngError> {
+        let typ = PngChunkType::PLTE;
+        let data = data_of(chunk, &typ)?;
+        if data.is_empty() || data.len() % 3 != 0 || data.len() > 256 * 3 {
+            return Err(invalid(&typ, "length must be a multiple of 3, up to 768"));
+        }
+        let entries = data
+            .chunks_exact(3)
+            .map(|rgb| [rgb[0], rgb[1], rgb[2]])
+            .collect();
+        Ok(Self { entries })
+    }
+
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when there are no entries or more than 256.
+    pub fn to_chunk(&self) -> Result<PngChunk, PngError> {
+        let typ = PngChunkType::PLTE;
+        if self.entries.is_empty() || self.entries.len() > 256 {
+            return Err(invalid(&typ, "palette must have 1 to 256 entries"));
+        }
+        let data = self.entries.concat();
+        Ok(PngChunk::with_crc(typ, data))
+    }
+}
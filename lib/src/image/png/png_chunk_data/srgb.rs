@@ -0,0 +1,34 @@
+use super::{fixed_len_data_of, invalid, PngChunk, PngChunkType, PngError};
+
+/// Standard RGB color space ("sRGB" chunk).
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Srgb {
+    pub rendering_intent: RenderingIntent,
+}
+
+impl Srgb {
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when `chunk` is not a valid sRGB chunk.
+    pub fn from_chunk(chunk: &PngChunk) -> Result<Self, PngError> {
+        let typ = PngChunkType::SRGB;
+        let data = fixed_len_data_of(chunk, &typ, 1)?;
+        let rendering_intent = match data[0] {
+            0 => RenderingIntent::Perceptual,
+            1 => RenderingIntent::RelativeColorimetric,
+            2 => RenderingIntent::Saturation,
+            3 => RenderingIntent::AbsoluteColorimetric,
+            _ => return Err(invalid(&typ, "unknown rendering intent")),
+        };
+        Ok(Self { rendering_intent })
+    }
+}
+
+/// Rendering intent in sRGB chunk.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum RenderingIntent {
+    Perceptual,
+    RelativeColorimetric,
+    Saturation,
+    AbsoluteColorimetric,
+}
@@ -0,0 +1,53 @@
+use super::{
+    data_of, latin1_to_string, split_keyword, string_to_latin1, text_to_latin1, validate_keyword,
+    PngChunk, PngChunkType, PngError,
+};
+
+/// Uncompressed Latin-1 text ("tEXt" chunk).
+///
+/// # Examples
+///
+/// ```
+/// use lib::image::png::{PngChunk, Text};
+///
+/// let chunk = PngChunk::new_text_chunk("Copyright", "© 2021 yuk1ty").unwrap();
+/// assert_eq!(chunk.data(), b"Copyright\0\xA9 2021 yuk1ty");
+///
+/// let text = Text::from_chunk(&chunk).unwrap();
+/// assert_eq!(text.keyword, "Copyright");
+/// assert_eq!(text.text, "© 2021 yuk1ty");
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Text {
+    pub keyword: String,
+    pub text: String,
+}
+
+impl Text {
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when `chunk` is not a valid tEXt chunk.
+    pub fn from_chunk(chunk: &PngChunk) -> Result<Self, PngError> {
+        let typ = PngChunkType::TEXT;
+        let (keyword, text) = split_keyword(data_of(chunk, &typ)?, &typ)?;
+        Ok(Self {
+            keyword,
+            text: latin1_to_string(text),
+        })
+    }
+
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when keyword is invalid,
+    /// either keyword or text has characters out of Latin-1, or text contains a null character.
+    pub fn to_chunk(&self) -> Result<PngChunk, PngError> {
+        let typ = PngChunkType::TEXT;
+        validate_keyword(&self.keyword, &typ)?;
+        let text = text_to_latin1(&self.text, &typ)?;
+
+        let mut data = string_to_latin1(&self.keyword).unwrap_or_default();
+        data.push(0);
+        data.extend_from_slice(&text);
+        PngChunk::new(typ, data)
+    }
+}
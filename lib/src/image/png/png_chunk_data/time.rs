@@ -0,0 +1,43 @@
+use crate::binary::{self, Endian};
+
+use super::{fixed_len_data_of, invalid, PngChunk, PngChunkType, PngError};
+
+/// Last modification time in UTC ("tIME" chunk).
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Time {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    /// 60 is allowed for leap seconds.
+    pub second: u8,
+}
+
+impl Time {
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when `chunk` is not a valid tIME chunk.
+    pub fn from_chunk(chunk: &PngChunk) -> Result<Self, PngError> {
+        let typ = PngChunkType::TIME;
+        let mut data = fixed_len_data_of(chunk, &typ, 7)?;
+        let year = binary::read(&mut data, &Endian::BigEndian)?;
+        let (month, day, hour, minute, second) = (data[0], data[1], data[2], data[3], data[4]);
+        if !(1..=12).contains(&month)
+            || !(1..=31).contains(&day)
+            || hour > 23
+            || minute > 59
+            || second > 60
+        {
+            return Err(invalid(&typ, "date or time out of range"));
+        }
+        Ok(Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        })
+    }
+}
@@ -0,0 +1,84 @@
+use crate::binary::{self, Endian};
+
+use super::{
+    data_of, fixed_len_data_of, invalid, ColorType, Plte, PngChunk, PngChunkType, PngError,
+};
+
+/// Transparency ("tRNS" chunk).
+/// Layout depends on the color type of the image.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Trns {
+    /// Gray level to be treated as transparent.
+    Grayscale(u16),
+    /// RGB color to be treated as transparent.
+    Rgb(u16, u16, u16),
+    /// Alpha values for palette entries, in palette order.
+    Indexed(Vec<u8>),
+}
+
+impl Trns {
+    /// `plte` is the palette preceding `chunk`, if any.
+    ///
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when `chunk` is not a valid tRNS chunk
+    /// for an image of `color_type`, or has more entries than `plte`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::image::png::{ColorType, Plte, PngChunk, PngChunkType, PngError, Trns};
+    ///
+    /// let plte = Plte {
+    ///     entries: vec![[0xFF, 0, 0], [0, 0xFF, 0]],
+    /// };
+    /// let chunk = PngChunk::new(PngChunkType::TRNS, vec![0x00, 0x80]).unwrap();
+    /// let trns = Trns::from_chunk(&chunk, ColorType::Indexed, Some(&plte)).unwrap();
+    /// assert_eq!(trns, Trns::Indexed(vec![0x00, 0x80]));
+    ///
+    /// // 3 alpha values for 2 palette entries
+    /// let chunk = PngChunk::new(PngChunkType::TRNS, vec![0x00, 0x80, 0xFF]).unwrap();
+    /// assert!(matches!(
+    ///     Trns::from_chunk(&chunk, ColorType::Indexed, Some(&plte)),
+    ///     Err(PngError::InvalidChunkData { .. })
+    /// ));
+    /// ```
+    pub fn from_chunk(
+        chunk: &PngChunk,
+        color_type: ColorType,
+        plte: Option<&Plte>,
+    ) -> Result<Self, PngError> {
+        let typ = PngChunkType::TRNS;
+        match color_type {
+            ColorType::Grayscale => {
+                let mut data = fixed_len_data_of(chunk, &typ, 2)?;
+                Ok(Trns::Grayscale(binary::read(
+                    &mut data,
+                    &Endian::BigEndian,
+                )?))
+            }
+            ColorType::Rgb => {
+                let mut data = fixed_len_data_of(chunk, &typ, 6)?;
+                Ok(Trns::Rgb(
+                    binary::read(&mut data, &Endian::BigEndian)?,
+                    binary::read(&mut data, &Endian::BigEndian)?,
+                    binary::read(&mut data, &Endian::BigEndian)?,
+                ))
+            }
+            ColorType::Indexed => {
+                let data = data_of(chunk, &typ)?;
+                if data.len() > 256 {
+                    return Err(invalid(&typ, "more entries than a palette can have"));
+                }
+                if plte.is_some_and(|plte| data.len() > plte.entries.len()) {
+                    return Err(invalid(&typ, "more entries than the palette"));
+                }
+                Ok(Trns::Indexed(data.to_vec()))
+            }
+            ColorType::GrayscaleAlpha | ColorType::Rgba => Err(invalid(
+                &typ,
+                "not allowed for color types with alpha channel",
+            )),
+        }
+    }
+}
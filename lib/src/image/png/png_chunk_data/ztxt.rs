@@ -0,0 +1,84 @@
+use super::{
+    compress, data_of, decompress, invalid, latin1_to_string, split_keyword, string_to_latin1,
+    text_to_latin1, validate_keyword, PngChunk, PngChunkType, PngError,
+};
+
+/// Compressed Latin-1 text ("zTXt" chunk).
+///
+/// # Examples
+///
+/// ```
+/// use lib::image::png::{PngChunk, Ztxt};
+///
+/// let license = "Permission is hereby granted, free of charge, ...".repeat(20);
+/// let chunk = PngChunk::new_ztxt_chunk("License", &license).unwrap();
+/// assert!(chunk.data().len() < license.len());
+///
+/// let ztxt = Ztxt::from_chunk(&chunk).unwrap();
+/// assert_eq!(ztxt.keyword, "License");
+/// assert_eq!(ztxt.text, license);
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Ztxt {
+    pub keyword: String,
+    pub text: String,
+}
+
+impl Ztxt {
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when `chunk` is not a valid zTXt chunk
+    /// or its text expands to more than `MAX_DECOMPRESSED_TEXT_LEN` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flate2::{write::ZlibEncoder, Compression};
+    /// use lib::image::png::{PngChunk, PngChunkType, PngError, Ztxt, MAX_DECOMPRESSED_TEXT_LEN};
+    /// use std::io::Write;
+    ///
+    /// // a few KiB of zeros expanding to just over the limit
+    /// let mut compressed = ZlibEncoder::new(Vec::new(), Compression::best());
+    /// compressed
+    ///     .write_all(&vec![0u8; MAX_DECOMPRESSED_TEXT_LEN + 1])
+    ///     .unwrap();
+    /// let compressed = compressed.finish().unwrap();
+    /// assert!(compressed.len() < 64 * 1024);
+    ///
+    /// let mut data = b"Comment\0\0".to_vec();
+    /// data.extend_from_slice(&compressed);
+    /// let chunk = PngChunk::new(PngChunkType::ZTXT, data).unwrap();
+    /// assert!(matches!(
+    ///     Ztxt::from_chunk(&chunk),
+    ///     Err(PngError::InvalidChunkData { .. })
+    /// ));
+    /// ```
+    pub fn from_chunk(chunk: &PngChunk) -> Result<Self, PngError> {
+        let typ = PngChunkType::ZTXT;
+        let (keyword, rest) = split_keyword(data_of(chunk, &typ)?, &typ)?;
+        match rest.split_first() {
+            Some((0, compressed)) => Ok(Self {
+                keyword,
+                text: latin1_to_string(&decompress(compressed, &typ)?),
+            }),
+            Some(_) => Err(invalid(&typ, "unknown compression method")),
+            None => Err(invalid(&typ, "missing compression method")),
+        }
+    }
+
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when keyword is invalid,
+    /// either keyword or text has characters out of Latin-1, or text contains a null character.
+    pub fn to_chunk(&self) -> Result<PngChunk, PngError> {
+        let typ = PngChunkType::ZTXT;
+        validate_keyword(&self.keyword, &typ)?;
+        let text = text_to_latin1(&self.text, &typ)?;
+
+        let mut data = string_to_latin1(&self.keyword).unwrap_or_default();
+        data.push(0);
+        data.push(0); // compression method: zlib
+        data.extend_from_slice(&compress(&text)?);
+        PngChunk::new(typ, data)
+    }
+}
@@ -4,13 +4,18 @@ use std::io::Read;
 
 pub use png_chunk::{PngChunk, PngChunkType};
 
+use super::PngError;
+
 /// Iterator of PngChunk.
+///
+/// Iteration stops at the first malformed chunk.
+/// Use `TryPngChunks` to know why it stopped.
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct PngChunks<R>
 where
     R: Read,
 {
-    reader_at_next_chunk: R,
+    inner: TryPngChunks<R>,
 }
 
 impl<R> PngChunks<R>
@@ -19,9 +24,23 @@ where
 {
     pub fn new(reader_at_first_chunk: R) -> Self {
         Self {
-            reader_at_next_chunk: reader_at_first_chunk,
+            inner: TryPngChunks::new(reader_at_first_chunk),
+        }
+    }
+
+    /// Same as `new()` but also stops at corrupted chunks.
+    pub fn new_strict(reader_at_first_chunk: R) -> Self {
+        Self {
+            inner: TryPngChunks::new_strict(reader_at_first_chunk),
         }
     }
+
+    /// Iterator yielding nothing.
+    pub(super) fn empty(reader: R) -> Self {
+        let mut inner = TryPngChunks::new(reader);
+        inner.finished = true;
+        Self { inner }
+    }
 }
 
 impl<R> Iterator for PngChunks<R>
@@ -31,7 +50,65 @@ where
     type Item = PngChunk;
 
     fn next(&mut self) -> Option<PngChunk> {
-        PngChunk::from_reader(&mut self.reader_at_next_chunk)
-            .expect("IO error while reading PNG chunks")
+        self.inner.next()?.ok()
+    }
+}
+
+/// Iterator of `Result<PngChunk, PngError>`.
+///
+/// Yields `None` after EOF or the first error.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TryPngChunks<R>
+where
+    R: Read,
+{
+    reader_at_next_chunk: R,
+    strict: bool,
+    finished: bool,
+}
+
+impl<R> TryPngChunks<R>
+where
+    R: Read,
+{
+    pub fn new(reader_at_first_chunk: R) -> Self {
+        Self {
+            reader_at_next_chunk: reader_at_first_chunk,
+            strict: false,
+            finished: false,
+        }
+    }
+
+    /// Same as `new()` but also yields `PngError::CrcMismatch` for corrupted chunks.
+    pub fn new_strict(reader_at_first_chunk: R) -> Self {
+        Self {
+            strict: true,
+            ..Self::new(reader_at_first_chunk)
+        }
+    }
+}
+
+impl<R> Iterator for TryPngChunks<R>
+where
+    R: Read,
+{
+    type Item = Result<PngChunk, PngError>;
+
+    fn next(&mut self) -> Option<Result<PngChunk, PngError>> {
+        if self.finished {
+            return None;
+        }
+        let mut res = PngChunk::from_reader(&mut self.reader_at_next_chunk).transpose();
+        if self.strict {
+            if let Some(Ok(chunk)) = &res {
+                if let Err(e) = chunk.verify_crc() {
+                    res = Some(Err(e));
+                }
+            }
+        }
+        if !matches!(res, Some(Ok(_))) {
+            self.finished = true;
+        }
+        res
     }
 }
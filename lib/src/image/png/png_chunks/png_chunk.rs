@@ -1,12 +1,20 @@
 mod png_chunk_type;
 
 use std::{
+    convert::TryFrom,
     fmt::Display,
-    io::{self, Read},
+    io::{self, Read, Write},
 };
 
 pub use png_chunk_type::PngChunkType;
 
+use crate::binary::{self, Endian};
+
+use super::super::{Itxt, PngError, Text, Ztxt};
+
+/// Chunk length must not exceed 2^31 - 1 bytes.
+const MAX_CHUNK_LEN: u32 = 0x7FFF_FFFF;
+
 /// Represents a chunk of PNG format.
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct PngChunk {
@@ -17,38 +25,161 @@ pub struct PngChunk {
 }
 
 impl PngChunk {
-    /// Creates "tEXt" chunk
-    pub fn new_text_chunk(text: String) -> Self {
-        let len = text.len() as u32;
-        let typ = PngChunkType::new([b't', b'E', b'X', b't']);
-        let data = text.into_bytes();
-        let crc = {
-            let mut hasher = crc32fast::Hasher::new();
-            hasher.update(&data);
-            let crc32 = hasher.finalize();
-            crc32.to_be_bytes()
-        };
+    /// Creates a chunk whose CRC is computed over `typ` and `data`.
+    ///
+    /// # Failures
+    ///
+    /// `PngError::OversizedLength` when `data` is longer than 2^31 - 1 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::image::png::PngChunk;
+    ///
+    /// let text = PngChunk::new_text_chunk("Comment", "ASCII PROGRAMMING++").unwrap();
+    /// let chunk = PngChunk::new(text.typ().clone(), b"Comment\0hello".to_vec()).unwrap();
+    /// assert!(chunk.verify_crc().is_ok());
+    /// // CRC covers chunk type as well as data
+    /// let mut hasher = crc32fast::Hasher::new();
+    /// hasher.update(b"tEXtComment\0hello");
+    /// assert_eq!(chunk.crc(), hasher.finalize());
+    /// ```
+    pub fn new(typ: PngChunkType, data: Vec<u8>) -> Result<Self, PngError> {
+        if data.len() > MAX_CHUNK_LEN as usize {
+            return Err(PngError::OversizedLength(
+                u32::try_from(data.len()).unwrap_or(u32::MAX),
+            ));
+        }
+        Ok(Self::with_crc(typ, data))
+    }
+
+    /// Creates "tEXt" chunk. See `Text` for details.
+    ///
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when keyword is invalid,
+    /// either keyword or text has characters out of Latin-1, or text contains a null character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::image::png::{PngChunk, PngError};
+    ///
+    /// assert!(PngChunk::new_text_chunk("Comment", "a b").is_ok());
+    /// for text in &["a\0b", "日本語"] {
+    ///     assert!(matches!(
+    ///         PngChunk::new_text_chunk("Comment", text),
+    ///         Err(PngError::InvalidChunkData { .. })
+    ///     ));
+    ///     assert!(PngChunk::new_ztxt_chunk("Comment", text).is_err());
+    /// }
+    /// ```
+    pub fn new_text_chunk(keyword: &str, text: &str) -> Result<Self, PngError> {
+        Text {
+            keyword: keyword.to_string(),
+            text: text.to_string(),
+        }
+        .to_chunk()
+    }
+
+    /// Creates "zTXt" chunk. See `Ztxt` for details.
+    ///
+    /// # Failures
+    ///
+    /// Same as `new_text_chunk()`.
+    pub fn new_ztxt_chunk(keyword: &str, text: &str) -> Result<Self, PngError> {
+        Ztxt {
+            keyword: keyword.to_string(),
+            text: text.to_string(),
+        }
+        .to_chunk()
+    }
+
+    /// Creates "iTXt" chunk, whose text is compressed if `compressed`. See `Itxt` for details.
+    ///
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkData` when keyword is invalid or
+    /// language tag is not ASCII or any field other than text contains a null character.
+    pub fn new_itxt_chunk(
+        keyword: &str,
+        language_tag: &str,
+        translated_keyword: &str,
+        text: &str,
+        compressed: bool,
+    ) -> Result<Self, PngError> {
+        Itxt {
+            keyword: keyword.to_string(),
+            compressed,
+            language_tag: language_tag.to_string(),
+            translated_keyword: translated_keyword.to_string(),
+            text: text.to_string(),
+        }
+        .to_chunk()
+    }
+
+    pub fn typ(&self) -> &PngChunkType {
+        &self.typ
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// CRC stored in the chunk.
+    pub fn crc(&self) -> u32 {
+        u32::from_be_bytes(self.crc)
+    }
+
+    /// Checks the stored CRC against the one computed over chunk type and data.
+    ///
+    /// # Failures
+    ///
+    /// `PngError::CrcMismatch` when they differ.
+    pub fn verify_crc(&self) -> Result<(), PngError> {
+        let stored = self.crc();
+        let computed = Self::compute_crc(&self.typ, &self.data);
+        if stored == computed {
+            Ok(())
+        } else {
+            Err(PngError::CrcMismatch { stored, computed })
+        }
+    }
+
+    /// Same as `new()` for callers which know `data` is short enough.
+    pub(in super::super) fn with_crc(typ: PngChunkType, data: Vec<u8>) -> Self {
+        let crc = Self::compute_crc(&typ, &data).to_be_bytes();
         Self {
-            len,
+            len: data.len() as u32,
             typ,
             crc,
             data,
         }
     }
 
+    fn compute_crc(typ: &PngChunkType, data: &[u8]) -> u32 {
+        let mut hasher = crc32fast::Hasher::new();
+        hasher.update(typ.as_bytes());
+        hasher.update(data);
+        hasher.finalize()
+    }
+
     /// # Returns
     ///
     /// None when `r` points to EOF
-    pub(super) fn from_reader<R>(r: &mut R) -> io::Result<Option<Self>>
+    ///
+    /// # Failures
+    ///
+    /// - `PngError::TruncatedChunk` when `r` reaches EOF in the middle of a chunk.
+    /// - `PngError::OversizedLength` when the length field exceeds 2^31 - 1.
+    /// - `PngError::InvalidChunkType` when the chunk type is not 4 ASCII letters.
+    pub(super) fn from_reader<R>(r: &mut R) -> Result<Option<Self>, PngError>
     where
         R: Read,
     {
-        match Self::read_len(r) {
-            Err(e) => match e.kind() {
-                io::ErrorKind::UnexpectedEof => Ok(None),
-                _ => Err(e),
-            },
-            Ok(len) => {
+        match Self::read_len(r)? {
+            None => Ok(None),
+            Some(len) => {
                 let typ = Self::read_type(r)?;
                 let data = Self::read_data(r, len)?;
                 let crc = Self::read_crc(r)?;
@@ -62,50 +193,119 @@ impl PngChunk {
         }
     }
 
-    pub(in super::super) fn into_vec(mut self) -> Vec<u8> {
-        let mut bin = self.len.to_be_bytes().to_vec();
-        bin.append(&mut self.typ.as_slice().to_vec());
-        bin.append(&mut self.data);
-        bin.append(&mut self.crc.to_vec());
+    /// Writes length, type, data and stored CRC to `w`.
+    pub fn write_to<W>(&self, w: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        binary::write(w, self.len, &Endian::BigEndian)?;
+        w.write_all(self.typ.as_bytes())?;
+        w.write_all(&self.data)?;
+        w.write_all(&self.crc)
+    }
+
+    /// Writes a chunk made of `typ` and `data` to `w` without constructing `PngChunk`.
+    ///
+    /// # Failures
+    ///
+    /// `PngError::OversizedLength` when `data` is longer than 2^31 - 1 bytes.
+    pub(in super::super) fn write_parts<W>(
+        w: &mut W,
+        typ: &PngChunkType,
+        data: &[u8],
+    ) -> Result<(), PngError>
+    where
+        W: Write,
+    {
+        let len = u32::try_from(data.len())
+            .ok()
+            .filter(|len| *len <= MAX_CHUNK_LEN)
+            .ok_or(PngError::OversizedLength(
+                u32::try_from(data.len()).unwrap_or(u32::MAX),
+            ))?;
+        binary::write(w, len, &Endian::BigEndian)?;
+        w.write_all(typ.as_bytes())?;
+        w.write_all(data)?;
+        binary::write(w, Self::compute_crc(typ, data), &Endian::BigEndian)?;
+        Ok(())
+    }
+
+    pub(in super::super) fn into_vec(self) -> Vec<u8> {
+        let mut bin = Vec::<u8>::with_capacity(self.data.len() + 12);
+        self.write_to(&mut bin).expect("writing to Vec never fails");
         bin
     }
 
-    fn read_len<R>(r: &mut R) -> io::Result<u32>
+    /// Returns None when `r` is at EOF before the first byte of the length field.
+    fn read_len<R>(r: &mut R) -> Result<Option<u32>, PngError>
     where
         R: Read,
     {
         let mut buf = [0u8; 4];
-        r.read_exact(&mut buf)?;
-        Ok(u32::from_be_bytes(buf))
+        let mut filled = 0;
+        while filled < buf.len() {
+            match r.read(&mut buf[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        match filled {
+            0 => Ok(None),
+            4 => {
+                let len = u32::from_be_bytes(buf);
+                if len > MAX_CHUNK_LEN {
+                    Err(PngError::OversizedLength(len))
+                } else {
+                    Ok(Some(len))
+                }
+            }
+            _ => Err(PngError::TruncatedChunk),
+        }
     }
 
-    fn read_type<R>(r: &mut R) -> io::Result<PngChunkType>
+    fn read_type<R>(r: &mut R) -> Result<PngChunkType, PngError>
     where
         R: Read,
     {
         let mut buf = [0u8; 4];
-        r.read_exact(&mut buf)?;
-        Ok(PngChunkType::new(buf))
+        Self::read_exact(r, &mut buf)?;
+        PngChunkType::new(buf)
     }
 
-    fn read_data<R>(r: &mut R, len: u32) -> io::Result<Vec<u8>>
+    fn read_data<R>(r: &mut R, len: u32) -> Result<Vec<u8>, PngError>
     where
         R: Read,
     {
+        // Not allocating `len` bytes up front: a corrupted length must not exhaust memory.
         let mut buf = Vec::<u8>::new();
-        buf.resize(len as usize, 0);
-        r.read_exact(&mut buf)?;
-        Ok(buf)
+        r.take(len as u64).read_to_end(&mut buf)?;
+        if buf.len() == len as usize {
+            Ok(buf)
+        } else {
+            Err(PngError::TruncatedChunk)
+        }
     }
 
-    fn read_crc<R>(r: &mut R) -> io::Result<[u8; 4]>
+    fn read_crc<R>(r: &mut R) -> Result<[u8; 4], PngError>
     where
         R: Read,
     {
         let mut buf = [0u8; 4];
-        r.read_exact(&mut buf)?;
+        Self::read_exact(r, &mut buf)?;
         Ok(buf)
     }
+
+    fn read_exact<R>(r: &mut R, buf: &mut [u8]) -> Result<(), PngError>
+    where
+        R: Read,
+    {
+        r.read_exact(buf).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => PngError::TruncatedChunk,
+            _ => e.into(),
+        })
+    }
 }
 
 impl Display for PngChunk {
@@ -114,12 +314,19 @@ impl Display for PngChunk {
             "Chunk type: {}, Data len: {}, CRC: {:#X}",
             self.typ,
             self.len,
-            u32::from_be_bytes(self.crc)
+            self.crc()
         );
-        if self.typ.is_text() {
-            s = format!(r#"{} - "{}""#, s, String::from_utf8(self.data.clone()).expect(
-                "tEXt chunk can have printable Latin-1 text so conversion into UTF-8 may fail here",
-            ));
+        let keyword_text = if self.typ == PngChunkType::TEXT {
+            Text::from_chunk(self).ok().map(|t| (t.keyword, t.text))
+        } else if self.typ == PngChunkType::ZTXT {
+            Ztxt::from_chunk(self).ok().map(|t| (t.keyword, t.text))
+        } else if self.typ == PngChunkType::ITXT {
+            Itxt::from_chunk(self).ok().map(|t| (t.keyword, t.text))
+        } else {
+            None
+        };
+        if let Some((keyword, text)) = keyword_text {
+            s = format!(r#"{} - {}: "{}""#, s, keyword, text);
         }
         write!(f, "{}", s)
     }
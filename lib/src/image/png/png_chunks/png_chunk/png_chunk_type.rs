@@ -1,28 +1,168 @@
-use std::fmt::Display;
+use std::{convert::TryFrom, fmt::Display, str::FromStr};
+
+use super::super::super::PngError;
 
 /// PNG's chunk types.
 /// "IHDR", "sRGB", for example.
+///
+/// Always consists of 4 ASCII letters, whose case bits tell the chunk's properties.
+///
+/// # Examples
+///
+/// ```
+/// use lib::image::png::PngChunkType;
+///
+/// let typ: PngChunkType = "prVt".parse().unwrap();
+/// assert!(typ.is_ancillary());
+/// assert!(typ.is_private());
+/// assert!(!typ.is_reserved());
+/// assert!(typ.is_safe_to_copy());
+/// assert_eq!(typ.to_string(), "prVt");
+///
+/// assert!(PngChunkType::new(*b"IH\0R").is_err());
+/// assert!("IHDRX".parse::<PngChunkType>().is_err());
+/// ```
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct PngChunkType([u8; 4]);
 
 impl PngChunkType {
+    // Critical chunks
+    pub const IHDR: Self = Self(*b"IHDR");
+    pub const PLTE: Self = Self(*b"PLTE");
+    pub const IDAT: Self = Self(*b"IDAT");
+    pub const IEND: Self = Self(*b"IEND");
+
+    // Ancillary chunks in the PNG specification
+    pub const CHRM: Self = Self(*b"cHRM");
+    pub const CICP: Self = Self(*b"cICP");
+    pub const CLLI: Self = Self(*b"cLLI");
+    pub const GAMA: Self = Self(*b"gAMA");
+    pub const ICCP: Self = Self(*b"iCCP");
+    pub const MDCV: Self = Self(*b"mDCV");
+    pub const SBIT: Self = Self(*b"sBIT");
+    pub const SRGB: Self = Self(*b"sRGB");
+    pub const BKGD: Self = Self(*b"bKGD");
+    pub const HIST: Self = Self(*b"hIST");
+    pub const TRNS: Self = Self(*b"tRNS");
+    pub const EXIF: Self = Self(*b"eXIf");
+    pub const PHYS: Self = Self(*b"pHYs");
+    pub const SPLT: Self = Self(*b"sPLT");
+    pub const TIME: Self = Self(*b"tIME");
+    pub const ITXT: Self = Self(*b"iTXt");
+    pub const TEXT: Self = Self(*b"tEXt");
+    pub const ZTXT: Self = Self(*b"zTXt");
+    pub const ACTL: Self = Self(*b"acTL");
+    pub const FCTL: Self = Self(*b"fcTL");
+    pub const FDAT: Self = Self(*b"fdAT");
+
+    // Registered extensions
+    pub const OFFS: Self = Self(*b"oFFs");
+    pub const PCAL: Self = Self(*b"pCAL");
+    pub const SCAL: Self = Self(*b"sCAL");
+    pub const GIFG: Self = Self(*b"gIFg");
+    pub const GIFT: Self = Self(*b"gIFt");
+    pub const GIFX: Self = Self(*b"gIFx");
+    pub const STER: Self = Self(*b"sTER");
+    pub const DSIG: Self = Self(*b"dSIG");
+    pub const FRAC: Self = Self(*b"fRAc");
+
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkType` when `bytes` has bytes other than ASCII letters.
+    pub fn new(bytes: [u8; 4]) -> Result<Self, PngError> {
+        if bytes.iter().all(u8::is_ascii_alphabetic) {
+            Ok(Self(bytes))
+        } else {
+            Err(PngError::InvalidChunkType(bytes))
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 4] {
+        &self.0
+    }
+
+    /// Whether textual chunk: "tEXt", "zTXt" or "iTXt".
     pub fn is_text(&self) -> bool {
-        self.0 == "tEXt".as_bytes()
+        [Self::TEXT, Self::ZTXT, Self::ITXT].contains(self)
     }
 
-    pub(super) fn new(data: [u8; 4]) -> Self {
-        Self(data)
+    /// Whether critical chunk, which decoders must understand (first letter is uppercase).
+    pub fn is_critical(&self) -> bool {
+        !self.is_ancillary()
     }
 
-    pub(super) fn as_slice(&self) -> &[u8] {
-        &self.0
+    /// Whether ancillary chunk, which decoders may ignore (first letter is lowercase).
+    pub fn is_ancillary(&self) -> bool {
+        Self::property_bit(self.0[0])
+    }
+
+    /// Whether private chunk, not registered (second letter is lowercase).
+    pub fn is_private(&self) -> bool {
+        Self::property_bit(self.0[1])
+    }
+
+    /// Whether the reserved bit is set (third letter is lowercase).
+    /// Such chunks do not conform to the current specification and should be treated as unknown.
+    pub fn is_reserved(&self) -> bool {
+        Self::property_bit(self.0[2])
+    }
+
+    /// Whether editors may copy the chunk to a modified file even if they do not recognize it
+    /// (fourth letter is lowercase).
+    /// Unknown chunks which are not safe to copy depend on image data (IDAT) and critical chunks,
+    /// so they must be dropped when those are changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::image::png::PngChunkType;
+    ///
+    /// assert!(PngChunkType::TEXT.is_safe_to_copy());
+    /// // gamma is meaningless once pixels are edited
+    /// assert!(!PngChunkType::GAMA.is_safe_to_copy());
+    /// ```
+    pub fn is_safe_to_copy(&self) -> bool {
+        Self::property_bit(self.0[3])
+    }
+
+    /// Bit 5 of each byte (lowercase letter) is a property bit.
+    fn property_bit(byte: u8) -> bool {
+        byte & 0x20 != 0
+    }
+}
+
+impl TryFrom<[u8; 4]> for PngChunkType {
+    type Error = PngError;
+
+    fn try_from(bytes: [u8; 4]) -> Result<Self, Self::Error> {
+        Self::new(bytes)
+    }
+}
+
+impl FromStr for PngChunkType {
+    type Err = PngError;
+
+    /// # Failures
+    ///
+    /// - `PngError::InvalidChunkTypeLength` when `s` is not 4 bytes.
+    /// - `PngError::InvalidChunkType` when `s` has characters other than ASCII letters.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = <[u8; 4]>::try_from(s.as_bytes())
+            .map_err(|_| PngError::InvalidChunkTypeLength(s.len()))?;
+        Self::new(bytes)
+    }
+}
+
+impl TryFrom<&str> for PngChunkType {
+    type Error = PngError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
     }
 }
 
 impl Display for PngChunkType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let type_str = String::from_utf8(self.0.to_vec())
-            .expect("PNG's chunk types are in 4-byte printable ASCII.");
-        write!(f, "{}", type_str)
+        write!(f, "{}", self.0.escape_ascii())
     }
 }
@@ -1,10 +1,177 @@
-use super::{PngChunk, PNG_FILE_SIGNATURE};
+use super::{
+    png_apng::{self, FrameInput},
+    png_encoder::{self, EncodeOptions},
+    ColorType, PngChunk, PngError, PNG_FILE_SIGNATURE,
+};
 
 /// Creates a PNG binary.
 #[derive(Debug, Default)]
 pub struct PngCreator(Vec<PngChunk>);
 
 impl PngCreator {
+    /// Creates non-interlaced image from `pixels` with default `EncodeOptions`.
+    ///
+    /// `pixels` are scanlines from top to bottom in PNG's sample layout:
+    /// samples of bit depth 16 in big endian, samples of bit depth less than 8
+    /// packed into bytes from the most significant bit, each scanline padded to a byte boundary.
+    ///
+    /// # Failures
+    ///
+    /// - `PngError::InvalidChunkData` when dimensions or bit depth are not allowed.
+    /// - `PngError::InvalidImageData` when `pixels` has wrong length.
+    /// - `PngError::MissingChunk` for `ColorType::Indexed` (palette is given by options).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::image::png::{ColorType, PixelBuffer, PngAnalyzer, PngCreator};
+    ///
+    /// // 2x2 RGB image
+    /// let pixels = [
+    ///     0xFF, 0x00, 0x00, 0x00, 0xFF, 0x00,
+    ///     0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+    /// ];
+    /// let png = PngCreator::from_pixels(2, 2, ColorType::Rgb, 8, &pixels)
+    ///     .unwrap()
+    ///     .finalize();
+    ///
+    /// let image = PngAnalyzer::new_strict(png.as_slice()).decode().unwrap();
+    /// assert_eq!(
+    ///     image.pixels,
+    ///     PixelBuffer::Rgba8(vec![
+    ///         0xFF, 0x00, 0x00, 0xFF, 0x00, 0xFF, 0x00, 0xFF,
+    ///         0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    ///     ])
+    /// );
+    /// ```
+    pub fn from_pixels(
+        width: u32,
+        height: u32,
+        color_type: ColorType,
+        bit_depth: u8,
+        pixels: &[u8],
+    ) -> Result<Self, PngError> {
+        Self::from_pixels_with_options(
+            width,
+            height,
+            color_type,
+            bit_depth,
+            pixels,
+            &EncodeOptions::default(),
+        )
+    }
+
+    /// Same as `from_pixels()` but with filter strategy, IDAT chunk size and so on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::image::png::{
+    ///     ColorType, EncodeOptions, FilterStrategy, FilterType, PngAnalyzer, PngCreator, Plte,
+    /// };
+    ///
+    /// let options = EncodeOptions {
+    ///     filter: FilterStrategy::Fixed(FilterType::Paeth),
+    ///     idat_chunk_size: 16,
+    ///     palette: Some(Plte { entries: vec![[0, 0, 0], [0xFF, 0xFF, 0xFF]] }),
+    ///     ..EncodeOptions::default()
+    /// };
+    /// // 64x64 checkerboard with 1 bit per pixel
+    /// let pixels: Vec<u8> = (0..64).flat_map(|y| vec![if y % 2 == 0 { 0xAA } else { 0x55 }; 8]).collect();
+    /// let png = PngCreator::from_pixels_with_options(64, 64, ColorType::Indexed, 1, &pixels, &options)
+    ///     .unwrap()
+    ///     .finalize();
+    ///
+    /// let types: Vec<String> = PngAnalyzer::new(png.as_slice())
+    ///     .chunks()
+    ///     .map(|c| c.typ().to_string())
+    ///     .collect();
+    /// assert_eq!(&types[..3], &["IHDR", "PLTE", "IDAT"]);
+    /// assert_eq!(types.last().unwrap(), "IEND");
+    /// ```
+    pub fn from_pixels_with_options(
+        width: u32,
+        height: u32,
+        color_type: ColorType,
+        bit_depth: u8,
+        pixels: &[u8],
+        options: &EncodeOptions,
+    ) -> Result<Self, PngError> {
+        let ihdr = png_encoder::ihdr(width, height, color_type, bit_depth);
+        png_encoder::encode(&ihdr, pixels, options).map(Self)
+    }
+
+    /// Creates APNG whose first frame is also the default image.
+    /// Each frame is encoded in the same way as `from_pixels_with_options()`.
+    ///
+    /// `num_plays` is the number of times to loop; 0 means infinitely.
+    ///
+    /// # Failures
+    ///
+    /// - `PngError::InvalidAnimation` when there are no frames, the first frame does not
+    ///   cover the whole canvas, or any frame region is empty or exceeds the canvas.
+    /// - Same as `from_pixels_with_options()` for each frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::image::png::{
+    ///     BlendOp, ColorType, DisposeOp, EncodeOptions, Fctl, FrameInput, PngAnalyzer, PngCreator,
+    ///     PngError,
+    /// };
+    ///
+    /// let fctl = |width, height, x_offset, y_offset| Fctl {
+    ///     sequence_number: 0,
+    ///     width,
+    ///     height,
+    ///     x_offset,
+    ///     y_offset,
+    ///     delay_num: 1,
+    ///     delay_den: 10,
+    ///     dispose_op: DisposeOp::None,
+    ///     blend_op: BlendOp::Source,
+    /// };
+    /// let background = vec![0x00; 4 * 4];
+    /// let dot = vec![0xFF; 2 * 2];
+    /// let frames = [
+    ///     FrameInput { fctl: fctl(4, 4, 0, 0), pixels: &background },
+    ///     FrameInput { fctl: fctl(2, 2, 1, 1), pixels: &dot },
+    ///     FrameInput { fctl: fctl(2, 2, 2, 2), pixels: &dot },
+    /// ];
+    /// let png = PngCreator::from_frames(4, 4, ColorType::Grayscale, 8, &frames, 0, &EncodeOptions::default())
+    ///     .unwrap()
+    ///     .finalize();
+    ///
+    /// let animation = PngAnalyzer::new_strict(png.as_slice()).animation().unwrap();
+    /// assert_eq!(animation.actl.num_frames, 3);
+    /// assert!(animation.default_image_is_first_frame);
+    /// let offsets: Vec<_> = animation.frames.iter().map(|f| (f.fctl.x_offset, f.fctl.y_offset)).collect();
+    /// assert_eq!(offsets, [(0, 0), (1, 1), (2, 2)]);
+    /// assert_eq!(animation.frames[1].fctl.delay(), 0.1);
+    ///
+    /// // frames of width or height 0 are rejected
+    /// for &(width, height) in &[(0, 2), (2, 0)] {
+    ///     let frames = [
+    ///         FrameInput { fctl: fctl(4, 4, 0, 0), pixels: &background },
+    ///         FrameInput { fctl: fctl(width, height, 1, 1), pixels: &[] },
+    ///     ];
+    ///     let res = PngCreator::from_frames(4, 4, ColorType::Grayscale, 8, &frames, 0, &EncodeOptions::default());
+    ///     assert!(matches!(res, Err(PngError::InvalidAnimation(_))));
+    /// }
+    /// ```
+    pub fn from_frames(
+        width: u32,
+        height: u32,
+        color_type: ColorType,
+        bit_depth: u8,
+        frames: &[FrameInput],
+        num_plays: u32,
+        options: &EncodeOptions,
+    ) -> Result<Self, PngError> {
+        let ihdr = png_encoder::ihdr(width, height, color_type, bit_depth);
+        png_apng::encode(&ihdr, frames, num_plays, options).map(Self)
+    }
+
     pub fn add_chunk(&mut self, chunk: PngChunk) {
         self.0.push(chunk)
     }
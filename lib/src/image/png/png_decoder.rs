@@ -0,0 +1,405 @@
+use std::{convert::TryFrom, io::Read};
+
+use flate2::read::ZlibDecoder;
+
+use super::{
+    png_filter::unfilter, ColorType, Ihdr, InterlaceMethod, Plte, PngChunk, PngChunkType, PngError,
+    Trns,
+};
+
+/// Decoded image.
+///
+/// Made by `PngAnalyzer::decode()`.
+///
+/// # Examples
+///
+/// ```
+/// use flate2::{write::ZlibEncoder, Compression};
+/// use lib::image::png::{
+///     ColorType, Ihdr, InterlaceMethod, PixelBuffer, Plte, PngAnalyzer, PngChunk, PngChunkType,
+///     PngImage, PngWriter,
+/// };
+/// use std::io::Write;
+///
+/// /// Decodes PNG made of `ihdr`, `chunks` and IDAT holding `raw` (filtered scanlines).
+/// fn decode(ihdr: Ihdr, chunks: &[PngChunk], raw: &[u8]) -> PngImage {
+///     let mut png = PngWriter::new(Vec::<u8>::new()).unwrap();
+///     png.write_chunk(&ihdr.to_chunk().unwrap()).unwrap();
+///     for chunk in chunks {
+///         png.write_chunk(chunk).unwrap();
+///     }
+///     let mut idat = ZlibEncoder::new(Vec::new(), Compression::default());
+///     idat.write_all(raw).unwrap();
+///     png.write_chunk_data(&PngChunkType::IDAT, &idat.finish().unwrap())
+///         .unwrap();
+///     let bin = png.finish().unwrap();
+///     PngAnalyzer::new(bin.as_slice()).decode().unwrap()
+/// }
+///
+/// fn ihdr(width: u32, height: u32, bit_depth: u8, color_type: ColorType) -> Ihdr {
+///     Ihdr {
+///         width,
+///         height,
+///         bit_depth,
+///         color_type,
+///         interlace_method: InterlaceMethod::None,
+///     }
+/// }
+///
+/// fn gray(levels: &[u8]) -> PixelBuffer {
+///     PixelBuffer::Rgba8(levels.iter().flat_map(|&g| vec![g, g, g, 0xFF]).collect())
+/// }
+///
+/// // filter types Sub, Up, Average and Paeth, one per row
+/// let raw = [
+///     1, 10, 10, 10, // 10 20 30
+///     2, 5, 5, 5, // 15 25 35
+///     3, 13, 8, 8, // 20 30 40
+///     4, 5, 231, 45, // 25 5 50
+/// ];
+/// let image = decode(ihdr(3, 4, 8, ColorType::Grayscale), &[], &raw);
+/// assert_eq!(
+///     image.pixels,
+///     gray(&[10, 20, 30, 15, 25, 35, 20, 30, 40, 25, 5, 50])
+/// );
+///
+/// // Adam7: 3x3 pixels are stored in passes 1, 4, 5, 6 (2 rows) and 7
+/// let adam7 = Ihdr {
+///     interlace_method: InterlaceMethod::Adam7,
+///     ..ihdr(3, 3, 8, ColorType::Grayscale)
+/// };
+/// let raw = [0, 0, 0, 20, 0, 60, 80, 0, 10, 0, 70, 0, 30, 40, 50];
+/// let image = decode(adam7, &[], &raw);
+/// assert_eq!(image.pixels, gray(&[0, 10, 20, 30, 40, 50, 60, 70, 80]));
+///
+/// // 16-bit RGB with filter Sub, relative to the byte 6 bytes (1 pixel) before
+/// let raw = [
+///     1, 0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0x00, 0x01, 0x00, 0x01, 0x00, 0x01,
+/// ];
+/// let image = decode(ihdr(2, 1, 16, ColorType::Rgb), &[], &raw);
+/// assert_eq!(
+///     image.pixels,
+///     PixelBuffer::Rgba16(vec![
+///         0x1234, 0x5678, 0x9ABC, 0xFFFF, 0x1235, 0x5679, 0x9ABD, 0xFFFF
+///     ])
+/// );
+///
+/// // grayscale samples of 1, 2 and 4 bits are scaled to 8 bits
+/// let image = decode(ihdr(3, 1, 1, ColorType::Grayscale), &[], &[0, 0b1010_0000]);
+/// assert_eq!(image.pixels, gray(&[0xFF, 0x00, 0xFF]));
+/// let image = decode(ihdr(3, 1, 2, ColorType::Grayscale), &[], &[0, 0b0001_1100]);
+/// assert_eq!(image.pixels, gray(&[0x00, 0x55, 0xFF]));
+/// let image = decode(ihdr(3, 1, 4, ColorType::Grayscale), &[], &[0, 0x08, 0xF0]);
+/// assert_eq!(image.pixels, gray(&[0x00, 0x88, 0xFF]));
+///
+/// // 2-bit palette indices 0, 1, 2 with alpha of the first 2 entries in tRNS
+/// let plte = Plte {
+///     entries: vec![[0xFF, 0x00, 0x00], [0x00, 0xFF, 0x00], [0x00, 0x00, 0xFF]],
+/// };
+/// let trns = PngChunk::new(PngChunkType::TRNS, vec![0x00, 0x80]).unwrap();
+/// let chunks = [plte.to_chunk().unwrap(), trns];
+/// let image = decode(ihdr(3, 1, 2, ColorType::Indexed), &chunks, &[0, 0b0001_1000]);
+/// assert_eq!(
+///     image.pixels,
+///     PixelBuffer::Rgba8(vec![
+///         0xFF, 0x00, 0x00, 0x00, 0x00, 0xFF, 0x00, 0x80, 0x00, 0x00, 0xFF, 0xFF
+///     ])
+/// );
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct PngImage {
+    pub width: u32,
+    pub height: u32,
+    /// Pixels in row-major order.
+    pub pixels: PixelBuffer,
+}
+
+/// RGBA pixels.
+///
+/// Images with bit depth 16 are decoded into `Rgba16`, others into `Rgba8`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PixelBuffer {
+    /// 4 bytes per pixel.
+    Rgba8(Vec<u8>),
+    /// 4 samples per pixel.
+    Rgba16(Vec<u16>),
+}
+
+impl PixelBuffer {
+    /// Stores `pixel` at index `at`, narrowing samples for `Rgba8` (which are at most 0xFF).
+    fn put(&mut self, at: usize, pixel: &[u16; 4]) {
+        match self {
+            PixelBuffer::Rgba8(rgba) => {
+                for (dst, &sample) in rgba[at..at + 4].iter_mut().zip(pixel) {
+                    *dst = sample as u8;
+                }
+            }
+            PixelBuffer::Rgba16(rgba) => rgba[at..at + 4].copy_from_slice(pixel),
+        }
+    }
+}
+
+/// Adam7 passes: `(x_start, y_start, x_step, y_step)`.
+const ADAM7_PASSES: [(usize, usize, usize, usize); 7] = [
+    (0, 0, 8, 8),
+    (4, 0, 8, 8),
+    (0, 4, 4, 8),
+    (2, 0, 4, 4),
+    (0, 2, 2, 4),
+    (1, 0, 2, 2),
+    (0, 1, 1, 2),
+];
+
+/// Decodes image from chunks in file order.
+pub(super) fn decode<I>(chunks: I) -> Result<PngImage, PngError>
+where
+    I: Iterator<Item = Result<PngChunk, PngError>>,
+{
+    let mut ihdr = None;
+    let mut plte = None;
+    let mut trns_chunk = None;
+    let mut idat = Vec::<u8>::new();
+
+    for chunk in chunks {
+        let chunk = chunk?;
+        let typ = chunk.typ();
+        if ihdr.is_none() {
+            if typ != &PngChunkType::IHDR {
+                return Err(PngError::MissingChunk(PngChunkType::IHDR));
+            }
+            ihdr = Some(Ihdr::from_chunk(&chunk)?);
+        } else if typ == &PngChunkType::PLTE {
+            plte = Some(Plte::from_chunk(&chunk)?);
+        } else if typ == &PngChunkType::TRNS {
+            trns_chunk = Some(chunk);
+        } else if typ == &PngChunkType::IDAT {
+            idat.extend_from_slice(chunk.data());
+        } else if typ == &PngChunkType::IEND {
+            break;
+        }
+    }
+
+    let ihdr = ihdr.ok_or(PngError::MissingChunk(PngChunkType::IHDR))?;
+    if idat.is_empty() {
+        return Err(PngError::MissingChunk(PngChunkType::IDAT));
+    }
+    if ihdr.color_type == ColorType::Indexed && plte.is_none() {
+        return Err(PngError::MissingChunk(PngChunkType::PLTE));
+    }
+    let trns = match trns_chunk {
+        Some(chunk) => Some(Trns::from_chunk(&chunk, ihdr.color_type, plte.as_ref())?),
+        None => None,
+    };
+
+    let passes = passes(&ihdr);
+    let raw = inflate(&idat, raw_len(&ihdr, &passes)?)?;
+
+    let converter = RgbaConverter {
+        ihdr: &ihdr,
+        plte: plte.as_ref(),
+        trns: trns.as_ref(),
+    };
+    let width = ihdr.width as usize;
+    let rgba_len = width * ihdr.height as usize * 4;
+    // 8-bit samples are stored as they are converted, not to hold a `u16` copy of the whole image
+    let mut pixels = if ihdr.bit_depth == 16 {
+        PixelBuffer::Rgba16(vec![0; rgba_len])
+    } else {
+        PixelBuffer::Rgba8(vec![0; rgba_len])
+    };
+
+    let mut raw = raw.as_slice();
+    for pass in passes.iter().filter(|p| !p.is_empty()) {
+        let stride = stride(&ihdr, pass.width);
+        let (pass_raw, rest) = raw.split_at((stride + 1) * pass.height);
+        raw = rest;
+
+        let scanlines = unfilter(pass_raw, stride, filter_unit(&ihdr))?;
+        for (row, scanline) in scanlines.chunks_exact(stride).enumerate() {
+            let y = pass.y_start + row * pass.y_step;
+            for col in 0..pass.width {
+                let x = pass.x_start + col * pass.x_step;
+                let pixel = converter.convert(scanline, col)?;
+                pixels.put((y * width + x) * 4, &pixel);
+            }
+        }
+    }
+
+    Ok(PngImage {
+        width: ihdr.width,
+        height: ihdr.height,
+        pixels,
+    })
+}
+
+/// Sub-image made by interlacing. Non-interlaced images have only one pass.
+#[derive(Clone, Debug)]
+pub(super) struct Pass {
+    pub(super) x_start: usize,
+    pub(super) y_start: usize,
+    pub(super) x_step: usize,
+    pub(super) y_step: usize,
+    /// Width in pixels of the sub-image.
+    pub(super) width: usize,
+    /// Height in pixels of the sub-image.
+    pub(super) height: usize,
+}
+
+impl Pass {
+    /// Passes with no pixels have no scanlines (not even filter type bytes).
+    pub(super) fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+}
+
+pub(super) fn passes(ihdr: &Ihdr) -> Vec<Pass> {
+    let (width, height) = (ihdr.width as usize, ihdr.height as usize);
+    match ihdr.interlace_method {
+        InterlaceMethod::None => vec![Pass {
+            x_start: 0,
+            y_start: 0,
+            x_step: 1,
+            y_step: 1,
+            width,
+            height,
+        }],
+        InterlaceMethod::Adam7 => ADAM7_PASSES
+            .iter()
+            .map(|&(x_start, y_start, x_step, y_step)| Pass {
+                x_start,
+                y_start,
+                x_step,
+                y_step,
+                width: (width + x_step - 1 - x_start) / x_step,
+                height: (height + y_step - 1 - y_start) / y_step,
+            })
+            .collect(),
+    }
+}
+
+fn bits_per_pixel(ihdr: &Ihdr) -> usize {
+    ihdr.color_type.channels() * ihdr.bit_depth as usize
+}
+
+/// Bytes of a scanline without its filter type byte.
+pub(super) fn stride(ihdr: &Ihdr, width: usize) -> usize {
+    (width * bits_per_pixel(ihdr)).div_ceil(8)
+}
+
+/// Distance in bytes to the corresponding byte of the previous pixel (at least 1).
+pub(super) fn filter_unit(ihdr: &Ihdr) -> usize {
+    bits_per_pixel(ihdr).div_ceil(8).max(1)
+}
+
+/// Length of decompressed image data, checking the image fits in memory.
+fn raw_len(ihdr: &Ihdr, passes: &[Pass]) -> Result<usize, PngError> {
+    let too_large = || PngError::InvalidImageData("image too large");
+    let rgba_len = (ihdr.width as usize)
+        .checked_mul(ihdr.height as usize)
+        .and_then(|n| n.checked_mul(4 * 2))
+        .ok_or_else(too_large)?;
+    isize::try_from(rgba_len).map_err(|_| too_large())?;
+
+    // Bounded by rgba_len above, so no overflow here.
+    Ok(passes
+        .iter()
+        .filter(|p| !p.is_empty())
+        .map(|p| (stride(ihdr, p.width) + 1) * p.height)
+        .sum())
+}
+
+/// Decompresses zlib stream into exactly `expected_len` bytes.
+fn inflate(compressed: &[u8], expected_len: usize) -> Result<Vec<u8>, PngError> {
+    let mut raw = Vec::<u8>::new();
+    // Reading 1 extra byte to detect surplus data.
+    ZlibDecoder::new(compressed)
+        .take(expected_len as u64 + 1)
+        .read_to_end(&mut raw)
+        .map_err(|_| PngError::InvalidImageData("corrupt zlib stream"))?;
+    if raw.len() == expected_len {
+        Ok(raw)
+    } else {
+        Err(PngError::InvalidImageData(
+            "decompressed size does not match image dimensions",
+        ))
+    }
+}
+
+/// Converts pixels in scanline into RGBA of the same bit depth (8 for bit depths less than 8).
+struct RgbaConverter<'a> {
+    ihdr: &'a Ihdr,
+    plte: Option<&'a Plte>,
+    trns: Option<&'a Trns>,
+}
+
+impl<'a> RgbaConverter<'a> {
+    fn convert(&self, scanline: &[u8], col: usize) -> Result<[u16; 4], PngError> {
+        let channels = self.ihdr.color_type.channels();
+        let mut s = [0u16; 4];
+        for (ch, sample) in s.iter_mut().enumerate().take(channels) {
+            *sample = self.sample(scanline, col * channels + ch);
+        }
+
+        let opaque = if self.ihdr.bit_depth == 16 {
+            0xFFFF
+        } else {
+            0xFF
+        };
+        let scale = |v: u16| self.scale_to_8bit(v);
+        let rgba = match self.ihdr.color_type {
+            ColorType::Grayscale => {
+                let alpha = match self.trns {
+                    Some(Trns::Grayscale(g)) if *g == s[0] => 0,
+                    _ => opaque,
+                };
+                let v = scale(s[0]);
+                [v, v, v, alpha]
+            }
+            ColorType::GrayscaleAlpha => [s[0], s[0], s[0], s[1]],
+            ColorType::Rgb => {
+                let alpha = match self.trns {
+                    Some(Trns::Rgb(r, g, b)) if (*r, *g, *b) == (s[0], s[1], s[2]) => 0,
+                    _ => opaque,
+                };
+                [s[0], s[1], s[2], alpha]
+            }
+            ColorType::Rgba => s,
+            ColorType::Indexed => {
+                let idx = s[0] as usize;
+                let [r, g, b] = self
+                    .plte
+                    .and_then(|plte| plte.entries.get(idx))
+                    .ok_or(PngError::InvalidImageData("palette index out of range"))?;
+                let alpha = match self.trns {
+                    Some(Trns::Indexed(alphas)) => alphas.get(idx).copied().unwrap_or(0xFF),
+                    _ => 0xFF,
+                };
+                [*r as u16, *g as u16, *b as u16, alpha as u16]
+            }
+        };
+        Ok(rgba)
+    }
+
+    /// `n`-th sample in scanline.
+    fn sample(&self, scanline: &[u8], n: usize) -> u16 {
+        match self.ihdr.bit_depth {
+            16 => u16::from_be_bytes([scanline[n * 2], scanline[n * 2 + 1]]),
+            8 => scanline[n] as u16,
+            depth => {
+                let depth = depth as usize;
+                let bit = n * depth;
+                let shift = 8 - depth - bit % 8;
+                let mask = (1u16 << depth) - 1;
+                (scanline[bit / 8] as u16 >> shift) & mask
+            }
+        }
+    }
+
+    /// Scales grayscale samples of bit depth 1, 2 or 4 into 0-255.
+    fn scale_to_8bit(&self, v: u16) -> u16 {
+        match self.ihdr.bit_depth {
+            1 | 2 | 4 => v * 0xFF / ((1 << self.ihdr.bit_depth) - 1),
+            _ => v,
+        }
+    }
+}
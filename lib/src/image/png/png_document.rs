@@ -0,0 +1,243 @@
+use std::io::Read;
+
+use super::{PngAnalyzer, PngChunk, PngChunkType, PngCreator, PngError};
+
+/// All chunks of a PNG file, editable while keeping the chunk order the specification requires:
+///
+/// - IHDR comes first and IEND comes last, each exactly once.
+/// - IDAT chunks exist and are contiguous.
+/// - PLTE comes at most once, before IDAT.
+/// - cHRM, gAMA, iCCP, sBIT and sRGB come before PLTE and IDAT.
+/// - bKGD, hIST and tRNS come after PLTE (if any) and before IDAT.
+/// - pHYs, sPLT and acTL come before IDAT.
+///
+/// Edits which would break the order fail with `PngError::InvalidChunkOrder`
+/// (or `PngError::MissingChunk` for removed IHDR, IDAT or IEND) and leave the document unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use lib::image::png::{ColorType, PngAnalyzer, PngChunk, PngChunkType, PngCreator, PngDocument};
+///
+/// let png = PngCreator::from_pixels(1, 1, ColorType::Grayscale, 8, &[0x7F]).unwrap().finalize();
+/// let mut doc = PngDocument::from_reader(png.as_slice()).unwrap();
+///
+/// // stamp watermark text
+/// let text = PngChunk::new_text_chunk("Comment", "watermarked").unwrap();
+/// doc.insert_after(&PngChunkType::IHDR, text).unwrap();
+///
+/// // gAMA must come before IDAT
+/// let gama = PngChunk::new(PngChunkType::GAMA, 45455u32.to_be_bytes().to_vec()).unwrap();
+/// assert!(doc.insert_after(&PngChunkType::IDAT, gama).is_err());
+///
+/// let types: Vec<String> = PngAnalyzer::new(doc.finalize().as_slice())
+///     .chunks()
+///     .map(|c| c.typ().to_string())
+///     .collect();
+/// assert_eq!(types, ["IHDR", "tEXt", "IDAT", "IEND"]);
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct PngDocument {
+    chunks: Vec<PngChunk>,
+}
+
+impl PngDocument {
+    /// Loads all chunks of PNG file.
+    ///
+    /// # Failures
+    ///
+    /// - `PngError::InvalidChunkOrder` or `PngError::MissingChunk`
+    ///   when chunks are not in the order described above.
+    /// - Errors from `PngAnalyzer::try_chunks()`.
+    pub fn from_reader<R>(reader: R) -> Result<Self, PngError>
+    where
+        R: Read,
+    {
+        let chunks = PngAnalyzer::new(reader)
+            .try_chunks()?
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_chunks(chunks)
+    }
+
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkOrder` or `PngError::MissingChunk`
+    /// when chunks are not in the order described above.
+    pub fn from_chunks(chunks: Vec<PngChunk>) -> Result<Self, PngError> {
+        validate_order(chunks.iter().map(PngChunk::typ))?;
+        Ok(Self { chunks })
+    }
+
+    pub fn chunks(&self) -> &[PngChunk] {
+        &self.chunks
+    }
+
+    /// Inserts `chunk` right after the last chunk of type `typ`.
+    ///
+    /// # Failures
+    ///
+    /// - `PngError::MissingChunk` when no chunk has type `typ`.
+    /// - `PngError::InvalidChunkOrder` when `chunk` cannot be placed there.
+    pub fn insert_after(&mut self, typ: &PngChunkType, chunk: PngChunk) -> Result<(), PngError> {
+        let at = self
+            .chunks
+            .iter()
+            .rposition(|c| c.typ() == typ)
+            .ok_or_else(|| PngError::MissingChunk(typ.clone()))?
+            + 1;
+        let types = self.chunks.iter().map(PngChunk::typ);
+        validate_order(
+            types
+                .clone()
+                .take(at)
+                .chain(Some(chunk.typ()))
+                .chain(types.skip(at)),
+        )?;
+        self.chunks.insert(at, chunk);
+        Ok(())
+    }
+
+    /// Removes all chunks of type `typ` and returns how many were removed.
+    ///
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkOrder` or `PngError::MissingChunk` when removing IHDR, IDAT or IEND.
+    pub fn remove_all(&mut self, typ: &PngChunkType) -> Result<usize, PngError> {
+        let before = self.chunks.len();
+        self.retain(|c| c.typ() != typ)?;
+        Ok(before - self.chunks.len())
+    }
+
+    /// Replaces the first chunk of the same type as `chunk` with `chunk`.
+    ///
+    /// # Failures
+    ///
+    /// `PngError::MissingChunk` when no chunk has the same type.
+    pub fn replace(&mut self, chunk: PngChunk) -> Result<(), PngError> {
+        let old = self
+            .chunks
+            .iter_mut()
+            .find(|c| c.typ() == chunk.typ())
+            .ok_or_else(|| PngError::MissingChunk(chunk.typ().clone()))?;
+        *old = chunk;
+        Ok(())
+    }
+
+    /// Keeps only chunks for which `f` returns true.
+    ///
+    /// # Failures
+    ///
+    /// `PngError::InvalidChunkOrder` or `PngError::MissingChunk` when removing IHDR, IDAT or IEND.
+    pub fn retain<F>(&mut self, mut f: F) -> Result<(), PngError>
+    where
+        F: FnMut(&PngChunk) -> bool,
+    {
+        let keep: Vec<bool> = self.chunks.iter().map(&mut f).collect();
+        validate_order(
+            self.chunks
+                .iter()
+                .zip(&keep)
+                .filter(|(_, keep)| **keep)
+                .map(|(c, _)| c.typ()),
+        )?;
+        let mut keep = keep.into_iter();
+        self.chunks.retain(|_| keep.next().unwrap_or(false));
+        Ok(())
+    }
+
+    /// Removes all ancillary chunks (metadata, transparency, color space and so on),
+    /// keeping only the critical IHDR, PLTE, IDAT and IEND.
+    pub fn strip_ancillary(&mut self) {
+        self.chunks.retain(|c| !c.typ().is_ancillary());
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        self.into_creator().finalize()
+    }
+
+    pub fn into_creator(self) -> PngCreator {
+        let mut creator = PngCreator::default();
+        for chunk in self.chunks {
+            creator.add_chunk(chunk);
+        }
+        creator
+    }
+}
+
+/// Checks chunk order described in `PngDocument`.
+fn validate_order<'c, I>(types: I) -> Result<(), PngError>
+where
+    I: Iterator<Item = &'c PngChunkType>,
+{
+    let err = |typ: &PngChunkType, reason| {
+        Err(PngError::InvalidChunkOrder {
+            typ: typ.clone(),
+            reason,
+        })
+    };
+    let before_plte = [
+        PngChunkType::CHRM,
+        PngChunkType::GAMA,
+        PngChunkType::ICCP,
+        PngChunkType::SBIT,
+        PngChunkType::SRGB,
+    ];
+    let after_plte = [PngChunkType::BKGD, PngChunkType::HIST, PngChunkType::TRNS];
+    let before_idat = [PngChunkType::PHYS, PngChunkType::SPLT, PngChunkType::ACTL];
+
+    let mut seen_ihdr = false;
+    let mut seen_plte = false;
+    let mut seen_after_plte = false;
+    let mut seen_idat = false;
+    let mut idat_ended = false;
+    let mut seen_iend = false;
+    for (i, typ) in types.enumerate() {
+        if seen_iend {
+            return err(typ, "found after IEND");
+        }
+        if (i == 0) != (typ == &PngChunkType::IHDR) {
+            return err(&PngChunkType::IHDR, "must be the first chunk, only once");
+        }
+        seen_ihdr = true;
+
+        if typ == &PngChunkType::IDAT {
+            if idat_ended {
+                return err(typ, "IDAT chunks must be contiguous");
+            }
+            seen_idat = true;
+        } else if seen_idat {
+            idat_ended = true;
+        }
+
+        if typ == &PngChunkType::IEND {
+            seen_iend = true;
+        } else if typ == &PngChunkType::PLTE {
+            if seen_plte || seen_idat {
+                return err(typ, "must come before IDAT, only once");
+            }
+            if seen_after_plte {
+                return err(typ, "must come before bKGD, hIST and tRNS");
+            }
+            seen_plte = true;
+        } else if before_plte.contains(typ) {
+            if seen_plte || seen_idat {
+                return err(typ, "must come before PLTE and IDAT");
+            }
+        } else if after_plte.contains(typ) || before_idat.contains(typ) {
+            if seen_idat {
+                return err(typ, "must come before IDAT");
+            }
+            seen_after_plte |= after_plte.contains(typ);
+        }
+    }
+
+    if !seen_ihdr {
+        Err(PngError::MissingChunk(PngChunkType::IHDR))
+    } else if !seen_idat {
+        Err(PngError::MissingChunk(PngChunkType::IDAT))
+    } else if !seen_iend {
+        Err(PngError::MissingChunk(PngChunkType::IEND))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use std::io::Write;
+
+use flate2::{write::ZlibEncoder, Compression};
+
+use super::{
+    png_decoder::{filter_unit, stride},
+    png_filter::{filter, FilterStrategy},
+    ColorType, Ihdr, InterlaceMethod, Plte, PngChunk, PngChunkType, PngError,
+};
+
+/// Options for `PngCreator::from_pixels_with_options()`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct EncodeOptions {
+    /// How to choose filter type of each scanline.
+    pub filter: FilterStrategy,
+    /// Maximum data length of each IDAT chunk (at least 1).
+    pub idat_chunk_size: usize,
+    /// zlib compression level from 0 (none) to 9 (best).
+    pub compression_level: u32,
+    /// Palette written as PLTE chunk. Required for `ColorType::Indexed`.
+    pub palette: Option<Plte>,
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        Self {
+            filter: FilterStrategy::default(),
+            idat_chunk_size: 8192,
+            compression_level: 6,
+            palette: None,
+        }
+    }
+}
+
+/// Encodes pixels into IHDR, PLTE (if any), IDAT and IEND chunks.
+///
+/// `pixels` are non-interlaced scanlines in PNG's sample layout, without filter type bytes.
+pub(super) fn encode(
+    ihdr: &Ihdr,
+    pixels: &[u8],
+    options: &EncodeOptions,
+) -> Result<Vec<PngChunk>, PngError> {
+    let mut chunks = vec![ihdr.to_chunk()?];
+    chunks.extend(palette_chunk(ihdr, options)?);
+    for data in split(&compress(ihdr, pixels, options)?, options) {
+        chunks.push(PngChunk::with_crc(PngChunkType::IDAT, data.to_vec()));
+    }
+    chunks.push(PngChunk::with_crc(PngChunkType::IEND, vec![]));
+    Ok(chunks)
+}
+
+/// PLTE chunk from `options`, checking it is given only when allowed.
+pub(super) fn palette_chunk(
+    ihdr: &Ihdr,
+    options: &EncodeOptions,
+) -> Result<Option<PngChunk>, PngError> {
+    match (&options.palette, ihdr.color_type) {
+        (Some(plte), ColorType::Indexed | ColorType::Rgb | ColorType::Rgba) => {
+            Ok(Some(plte.to_chunk()?))
+        }
+        (None, ColorType::Indexed) => Err(PngError::MissingChunk(PngChunkType::PLTE)),
+        (Some(_), _) => Err(PngError::InvalidChunkData {
+            typ: PngChunkType::PLTE,
+            reason: "not allowed for grayscale images",
+        }),
+        (None, _) => Ok(None),
+    }
+}
+
+/// Filters and compresses `pixels` of an image described by `ihdr` into zlib stream.
+pub(super) fn compress(
+    ihdr: &Ihdr,
+    pixels: &[u8],
+    options: &EncodeOptions,
+) -> Result<Vec<u8>, PngError> {
+    let stride = stride(ihdr, ihdr.width as usize);
+    let expected_len = (ihdr.height as usize).checked_mul(stride);
+    if expected_len != Some(pixels.len()) {
+        return Err(PngError::InvalidImageData(
+            "pixel buffer length does not match image dimensions",
+        ));
+    }
+
+    let filtered = filter(pixels, stride, filter_unit(ihdr), options.filter);
+    let level = Compression::new(options.compression_level.min(9));
+    let mut encoder = ZlibEncoder::new(Vec::<u8>::new(), level);
+    encoder.write_all(&filtered)?;
+    Ok(encoder.finish()?)
+}
+
+/// Splits compressed data into pieces of at most `options.idat_chunk_size` bytes.
+pub(super) fn split<'d>(data: &'d [u8], options: &EncodeOptions) -> std::slice::Chunks<'d, u8> {
+    data.chunks(options.idat_chunk_size.clamp(1, 0x7FFF_FFFB))
+}
+
+/// Non-interlaced IHDR.
+pub(super) fn ihdr(width: u32, height: u32, color_type: ColorType, bit_depth: u8) -> Ihdr {
+    Ihdr {
+        width,
+        height,
+        bit_depth,
+        color_type,
+        interlace_method: InterlaceMethod::None,
+    }
+}
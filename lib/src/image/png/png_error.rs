@@ -0,0 +1,88 @@
+use std::{error::Error, fmt::Display, io};
+
+use super::PngChunkType;
+
+/// Errors while reading PNG format.
+#[derive(Debug)]
+pub enum PngError {
+    /// Leading 8 bytes do not match PNG file signature.
+    BadSignature,
+    /// Input ended in the middle of a chunk.
+    TruncatedChunk,
+    /// CRC stored in a chunk differs from the one computed over its type and data.
+    CrcMismatch {
+        /// CRC stored in the chunk.
+        stored: u32,
+        /// CRC computed from the chunk's type and data.
+        computed: u32,
+    },
+    /// Chunk type contains bytes other than ASCII letters.
+    InvalidChunkType([u8; 4]),
+    /// Chunk type given as a string is not 4 bytes long.
+    InvalidChunkTypeLength(usize),
+    /// Chunk length exceeds 2^31 - 1 bytes.
+    OversizedLength(u32),
+    /// Chunk data violates the specification of its chunk type.
+    InvalidChunkData {
+        typ: PngChunkType,
+        reason: &'static str,
+    },
+    /// Required chunk is not found where it should be.
+    MissingChunk(PngChunkType),
+    /// Chunks are not in the order the specification requires.
+    InvalidChunkOrder {
+        typ: PngChunkType,
+        reason: &'static str,
+    },
+    /// APNG chunks (acTL, fcTL and fdAT) are inconsistent.
+    InvalidAnimation(&'static str),
+    /// Image data (IDAT) cannot be decoded.
+    InvalidImageData(&'static str),
+    IoError(io::Error),
+}
+
+impl From<io::Error> for PngError {
+    fn from(error: io::Error) -> Self {
+        PngError::IoError(error)
+    }
+}
+
+impl Display for PngError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PngError::BadSignature => write!(f, "wrong PNG file signature"),
+            PngError::TruncatedChunk => write!(f, "PNG chunk is truncated"),
+            PngError::CrcMismatch { stored, computed } => write!(
+                f,
+                "CRC mismatch: stored {:#010X}, computed {:#010X}",
+                stored, computed
+            ),
+            PngError::InvalidChunkType(typ) => {
+                write!(f, "invalid chunk type: \"{}\"", typ.escape_ascii())
+            }
+            PngError::InvalidChunkTypeLength(len) => {
+                write!(f, "chunk type must be 4 bytes, not {}", len)
+            }
+            PngError::OversizedLength(len) => write!(f, "chunk length too large: {}", len),
+            PngError::InvalidChunkData { typ, reason } => {
+                write!(f, "invalid {} chunk: {}", typ, reason)
+            }
+            PngError::MissingChunk(typ) => write!(f, "missing {} chunk", typ),
+            PngError::InvalidChunkOrder { typ, reason } => {
+                write!(f, "misplaced {} chunk: {}", typ, reason)
+            }
+            PngError::InvalidAnimation(reason) => write!(f, "invalid animation: {}", reason),
+            PngError::InvalidImageData(reason) => write!(f, "invalid image data: {}", reason),
+            PngError::IoError(e) => write!(f, "IO error: {}", e),
+        }
+    }
+}
+
+impl Error for PngError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PngError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
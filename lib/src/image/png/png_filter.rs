@@ -0,0 +1,150 @@
+use super::PngError;
+
+/// Filter type of a scanline.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum FilterType {
+    None,
+    Sub,
+    Up,
+    Average,
+    Paeth,
+}
+
+impl FilterType {
+    const ALL: [FilterType; 5] = [
+        FilterType::None,
+        FilterType::Sub,
+        FilterType::Up,
+        FilterType::Average,
+        FilterType::Paeth,
+    ];
+
+    pub fn from_u8(n: u8) -> Option<Self> {
+        Self::ALL.get(n as usize).copied()
+    }
+
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            FilterType::None => 0,
+            FilterType::Sub => 1,
+            FilterType::Up => 2,
+            FilterType::Average => 3,
+            FilterType::Paeth => 4,
+        }
+    }
+
+    /// Predicts a byte from the byte `unit` bytes before (`a`), the byte above (`b`)
+    /// and the byte above `a` (`c`).
+    fn predict(&self, a: u8, b: u8, c: u8) -> u8 {
+        match self {
+            FilterType::None => 0,
+            FilterType::Sub => a,
+            FilterType::Up => b,
+            FilterType::Average => ((a as u16 + b as u16) / 2) as u8,
+            FilterType::Paeth => paeth(a, b, c),
+        }
+    }
+}
+
+/// How to choose filter type for each scanline while encoding.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Default)]
+pub enum FilterStrategy {
+    /// Every scanline is left unfiltered.
+    None,
+    /// Every scanline is filtered with the given type.
+    Fixed(FilterType),
+    /// Each scanline is filtered with the type minimizing the sum of
+    /// filtered bytes as signed values (heuristic recommended by the specification).
+    #[default]
+    AdaptiveMinSum,
+}
+
+/// Filters scanlines of `stride` bytes each.
+/// Returns scanlines each of which is led by its filter type byte.
+pub(super) fn filter(
+    scanlines: &[u8],
+    stride: usize,
+    unit: usize,
+    strategy: FilterStrategy,
+) -> Vec<u8> {
+    let mut out = Vec::<u8>::with_capacity(scanlines.len() + scanlines.len() / stride);
+    let prior_zero = vec![0u8; stride];
+    let mut candidate = vec![0u8; stride];
+
+    for (row, cur) in scanlines.chunks_exact(stride).enumerate() {
+        let prior = if row == 0 {
+            prior_zero.as_slice()
+        } else {
+            &scanlines[(row - 1) * stride..row * stride]
+        };
+
+        let filter_type = match strategy {
+            FilterStrategy::None => FilterType::None,
+            FilterStrategy::Fixed(filter_type) => filter_type,
+            FilterStrategy::AdaptiveMinSum => *FilterType::ALL
+                .iter()
+                .min_by_key(|filter_type| {
+                    filter_line(cur, prior, unit, **filter_type, &mut candidate);
+                    candidate
+                        .iter()
+                        .map(|&b| (b as i8).unsigned_abs() as u64)
+                        .sum::<u64>()
+                })
+                .expect("FilterType::ALL is not empty"),
+        };
+
+        filter_line(cur, prior, unit, filter_type, &mut candidate);
+        out.push(filter_type.as_u8());
+        out.extend_from_slice(&candidate);
+    }
+    out
+}
+
+fn filter_line(cur: &[u8], prior: &[u8], unit: usize, filter_type: FilterType, out: &mut [u8]) {
+    for i in 0..cur.len() {
+        let a = if i >= unit { cur[i - unit] } else { 0 };
+        let c = if i >= unit { prior[i - unit] } else { 0 };
+        out[i] = cur[i].wrapping_sub(filter_type.predict(a, prior[i], c));
+    }
+}
+
+/// Reverts filters of scanlines, each of which is led by its filter type byte.
+/// Returns scanlines without filter type bytes.
+pub(super) fn unfilter(filtered: &[u8], stride: usize, unit: usize) -> Result<Vec<u8>, PngError> {
+    let mut out = Vec::<u8>::with_capacity(filtered.len() - filtered.len() / (stride + 1));
+    let prior_zero = vec![0u8; stride];
+
+    for (row, line) in filtered.chunks_exact(stride + 1).enumerate() {
+        let filter_type = FilterType::from_u8(line[0])
+            .ok_or(PngError::InvalidImageData("unknown filter type"))?;
+        let start = out.len();
+        out.extend_from_slice(&line[1..]);
+        let (prev_rows, cur) = out.split_at_mut(start);
+        let prior = if row == 0 {
+            prior_zero.as_slice()
+        } else {
+            &prev_rows[start - stride..]
+        };
+
+        for i in 0..stride {
+            let a = if i >= unit { cur[i - unit] } else { 0 };
+            let c = if i >= unit { prior[i - unit] } else { 0 };
+            cur[i] = cur[i].wrapping_add(filter_type.predict(a, prior[i], c));
+        }
+    }
+    Ok(out)
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let pa = (p - a as i16).abs();
+    let pb = (p - b as i16).abs();
+    let pc = (p - c as i16).abs();
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
@@ -0,0 +1,93 @@
+use super::{
+    Bkgd, Chrm, ColorType, Gama, Ihdr, Phys, Plte, PngChunk, PngChunkType, PngError, Srgb, Time,
+    Trns,
+};
+
+/// Summary of a PNG image: its header and the decoded common ancillary chunks.
+///
+/// Made by `PngAnalyzer::info()`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct PngInfo {
+    pub ihdr: Ihdr,
+    pub plte: Option<Plte>,
+    pub trns: Option<Trns>,
+    pub gama: Option<Gama>,
+    pub chrm: Option<Chrm>,
+    pub srgb: Option<Srgb>,
+    pub phys: Option<Phys>,
+    pub time: Option<Time>,
+    pub bkgd: Option<Bkgd>,
+}
+
+impl PngInfo {
+    /// Builds summary from chunks in file order.
+    ///
+    /// # Failures
+    ///
+    /// - `PngError::MissingChunk` when IHDR is not the first chunk,
+    ///   PLTE is absent for indexed-color images, or IDAT / IEND is absent.
+    /// - `PngError::InvalidChunkData` when a known chunk cannot be decoded.
+    /// - Errors from `chunks` themselves.
+    pub(super) fn from_chunks<I>(mut chunks: I) -> Result<Self, PngError>
+    where
+        I: Iterator<Item = Result<PngChunk, PngError>>,
+    {
+        let ihdr = match chunks.next().transpose()? {
+            Some(chunk) if chunk.typ() == &PngChunkType::IHDR => Ihdr::from_chunk(&chunk)?,
+            _ => return Err(PngError::MissingChunk(PngChunkType::IHDR)),
+        };
+        let color_type = ihdr.color_type;
+
+        let mut info = Self {
+            ihdr,
+            plte: None,
+            trns: None,
+            gama: None,
+            chrm: None,
+            srgb: None,
+            phys: None,
+            time: None,
+            bkgd: None,
+        };
+        let mut seen_idat = false;
+        let mut seen_iend = false;
+
+        for chunk in chunks {
+            let chunk = chunk?;
+            let typ = chunk.typ();
+            if typ == &PngChunkType::IDAT {
+                if color_type == ColorType::Indexed && info.plte.is_none() {
+                    return Err(PngError::MissingChunk(PngChunkType::PLTE));
+                }
+                seen_idat = true;
+            } else if typ == &PngChunkType::IEND {
+                seen_iend = true;
+                break;
+            } else if typ == &PngChunkType::PLTE {
+                info.plte = Some(Plte::from_chunk(&chunk)?);
+            } else if typ == &PngChunkType::TRNS {
+                info.trns = Some(Trns::from_chunk(&chunk, color_type, info.plte.as_ref())?);
+            } else if typ == &PngChunkType::GAMA {
+                info.gama = Some(Gama::from_chunk(&chunk)?);
+            } else if typ == &PngChunkType::CHRM {
+                info.chrm = Some(Chrm::from_chunk(&chunk)?);
+            } else if typ == &PngChunkType::SRGB {
+                info.srgb = Some(Srgb::from_chunk(&chunk)?);
+            } else if typ == &PngChunkType::PHYS {
+                info.phys = Some(Phys::from_chunk(&chunk)?);
+            } else if typ == &PngChunkType::TIME {
+                info.time = Some(Time::from_chunk(&chunk)?);
+            } else if typ == &PngChunkType::BKGD {
+                info.bkgd = Some(Bkgd::from_chunk(&chunk, color_type)?);
+            }
+        }
+
+        if !seen_idat {
+            Err(PngError::MissingChunk(PngChunkType::IDAT))
+        } else if !seen_iend {
+            Err(PngError::MissingChunk(PngChunkType::IEND))
+        } else {
+            Ok(info)
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::io::{self, Write};
+
+use super::{PngChunk, PngChunkType, PngError, PNG_FILE_SIGNATURE};
+
+/// Writes PNG signature and chunks straight to `W` as they are added,
+/// without holding the whole file in memory as `PngCreator` does.
+///
+/// # Examples
+///
+/// ```
+/// use flate2::{write::ZlibEncoder, Compression};
+/// use lib::image::png::{ColorType, Ihdr, InterlaceMethod, PngAnalyzer, PngWriter};
+/// use std::io::Write;
+///
+/// let (width, height) = (1024, 1024);
+/// let ihdr = Ihdr {
+///     width,
+///     height,
+///     bit_depth: 8,
+///     color_type: ColorType::Grayscale,
+///     interlace_method: InterlaceMethod::None,
+/// };
+///
+/// let mut png = PngWriter::new(Vec::<u8>::new()).unwrap();
+/// png.write_chunk(&ihdr.to_chunk().unwrap()).unwrap();
+/// {
+///     // scanlines are compressed and split into IDAT chunks row by row
+///     let mut idat = ZlibEncoder::new(png.idat_writer(4096), Compression::default());
+///     for y in 0..height {
+///         idat.write_all(&[0]).unwrap(); // filter type: None
+///         idat.write_all(&vec![y as u8; width as usize]).unwrap();
+///     }
+///     idat.finish().unwrap().finish().unwrap();
+/// }
+/// let bin = png.finish().unwrap();
+///
+/// let chunks: Vec<_> = PngAnalyzer::new_strict(bin.as_slice()).chunks().collect();
+/// assert!(chunks.iter().all(|c| c.data().len() <= 4096));
+/// assert_eq!(PngAnalyzer::new(bin.as_slice()).info().unwrap().ihdr.width, 1024);
+/// ```
+#[derive(Debug)]
+pub struct PngWriter<W>
+where
+    W: Write,
+{
+    writer: W,
+}
+
+impl<W> PngWriter<W>
+where
+    W: Write,
+{
+    /// Writes PNG file signature to `writer`.
+    pub fn new(mut writer: W) -> Result<Self, PngError> {
+        writer.write_all(&PNG_FILE_SIGNATURE)?;
+        Ok(Self { writer })
+    }
+
+    pub fn write_chunk(&mut self, chunk: &PngChunk) -> Result<(), PngError> {
+        chunk.write_to(&mut self.writer)?;
+        Ok(())
+    }
+
+    /// Writes a chunk of type `typ` whose CRC is computed over `typ` and `data`.
+    ///
+    /// # Failures
+    ///
+    /// `PngError::OversizedLength` when `data` is longer than 2^31 - 1 bytes.
+    pub fn write_chunk_data(&mut self, typ: &PngChunkType, data: &[u8]) -> Result<(), PngError> {
+        PngChunk::write_parts(&mut self.writer, typ, data)
+    }
+
+    /// Writer which splits written bytes into IDAT chunks of at most `max_chunk_len` bytes.
+    /// Bytes written to it must form a zlib stream.
+    pub fn idat_writer(&mut self, max_chunk_len: usize) -> IdatWriter<'_, W> {
+        let max_chunk_len = max_chunk_len.clamp(1, 0x7FFF_FFFF);
+        IdatWriter {
+            png: self,
+            // grows as needed, not to allocate up to 2 GiB for large `max_chunk_len` up front
+            buf: Vec::with_capacity(max_chunk_len.min(INITIAL_BUF_LEN)),
+            max_chunk_len,
+        }
+    }
+
+    /// Writes IEND chunk, flushes and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, PngError> {
+        self.write_chunk_data(&PngChunkType::IEND, &[])?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Initial capacity of the buffer of `IdatWriter`.
+const INITIAL_BUF_LEN: usize = 64 * 1024;
+
+/// Writer emitting IDAT chunks of bounded size. Made by `PngWriter::idat_writer()`.
+///
+/// Bytes are buffered up to the chunk size.
+/// Remaining bytes are emitted as the last IDAT chunk by `finish()`, `flush()` or drop
+/// (errors are ignored on drop).
+#[derive(Debug)]
+pub struct IdatWriter<'p, W>
+where
+    W: Write,
+{
+    png: &'p mut PngWriter<W>,
+    buf: Vec<u8>,
+    max_chunk_len: usize,
+}
+
+impl<'p, W> IdatWriter<'p, W>
+where
+    W: Write,
+{
+    /// Emits buffered bytes as IDAT chunk.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.emit_buffered()
+    }
+
+    fn emit_buffered(&mut self) -> io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        let mut buf = std::mem::take(&mut self.buf);
+        let res = self.emit(&buf);
+        buf.clear();
+        self.buf = buf; // reuses allocation
+        res
+    }
+
+    fn emit(&mut self, data: &[u8]) -> io::Result<()> {
+        self.png
+            .write_chunk_data(&PngChunkType::IDAT, data)
+            .map_err(|e| match e {
+                PngError::IoError(e) => e,
+                e => io::Error::new(io::ErrorKind::InvalidInput, e),
+            })
+    }
+}
+
+impl<'p, W> Write for IdatWriter<'p, W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.buf.is_empty() && buf.len() >= self.max_chunk_len {
+            // no need to copy into buffer
+            self.emit(&buf[..self.max_chunk_len])?;
+            return Ok(self.max_chunk_len);
+        }
+        let n = buf.len().min(self.max_chunk_len - self.buf.len());
+        self.buf.extend_from_slice(&buf[..n]);
+        if self.buf.len() == self.max_chunk_len {
+            self.emit_buffered()?;
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.emit_buffered()?;
+        self.png.writer.flush()
+    }
+}
+
+impl<'p, W> Drop for IdatWriter<'p, W>
+where
+    W: Write,
+{
+    fn drop(&mut self) {
+        let _ = self.emit_buffered();
+    }
+}
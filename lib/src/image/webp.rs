@@ -0,0 +1,9 @@
+//! Toolkit for analyzing WebP format (RIFF container).
+
+mod webp_analyzer;
+mod webp_chunks;
+mod webp_error;
+
+pub use webp_analyzer::WebpAnalyzer;
+pub use webp_chunks::{TryWebpChunks, WebpChunk, WebpChunks};
+pub use webp_error::WebpError;
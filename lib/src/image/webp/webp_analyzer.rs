@@ -0,0 +1,77 @@
+use std::io::{self, Read};
+
+use super::{TryWebpChunks, WebpChunks, WebpError};
+
+/// Reads RIFF header and chunks of WebP.
+///
+/// # Examples
+///
+/// ```
+/// use lib::image::webp::{WebpAnalyzer, WebpChunk, WebpError};
+///
+/// // RIFF header, VP8X (10 bytes) and EXIF (3 bytes, followed by a padding byte)
+/// let webp: &[u8] = &[
+///     b'R', b'I', b'F', b'F', 0x22, 0x00, 0x00, 0x00, b'W', b'E', b'B', b'P',
+///     b'V', b'P', b'8', b'X', 0x0A, 0x00, 0x00, 0x00,
+///     0x08, 0x00, 0x00, 0x00, 0x0F, 0x00, 0x00, 0x07, 0x00, 0x00,
+///     b'E', b'X', b'I', b'F', 0x03, 0x00, 0x00, 0x00, b'a', b'b', b'c', 0x00,
+/// ];
+///
+/// let chunks: Vec<_> = WebpAnalyzer::new(webp).chunks().collect();
+/// assert_eq!(chunks.len(), 2);
+/// assert_eq!(chunks[0].fourcc(), &WebpChunk::VP8X);
+/// assert_eq!(chunks[1].fourcc(), &WebpChunk::EXIF);
+/// assert_eq!(chunks[1].data(), b"abc");
+///
+/// let mut chunks = WebpAnalyzer::new(&webp[..35]).try_chunks().unwrap();
+/// assert!(chunks.next().unwrap().is_ok());
+/// assert!(matches!(chunks.next(), Some(Err(WebpError::TruncatedChunk))));
+/// ```
+#[derive(Debug)]
+pub struct WebpAnalyzer<R>
+where
+    R: Read,
+{
+    webp_reader: R,
+}
+
+impl<R> WebpAnalyzer<R>
+where
+    R: Read,
+{
+    pub fn new(webp_reader: R) -> Self {
+        Self { webp_reader }
+    }
+
+    /// Iterates chunks until the end of RIFF payload or the first malformed chunk.
+    ///
+    /// Yields nothing when RIFF header is wrong.
+    pub fn chunks(mut self) -> WebpChunks<R> {
+        let payload_len = self.read_header().unwrap_or(0);
+        WebpChunks::new(self.webp_reader, payload_len)
+    }
+
+    /// Iterates chunks, reporting malformed input as `WebpError`.
+    ///
+    /// # Failures
+    ///
+    /// `WebpError::BadSignature` when leading 12 bytes are not "RIFF", size and "WEBP".
+    pub fn try_chunks(mut self) -> Result<TryWebpChunks<R>, WebpError> {
+        let payload_len = self.read_header()?;
+        Ok(TryWebpChunks::new(self.webp_reader, payload_len))
+    }
+
+    /// Returns RIFF payload length following "WEBP".
+    fn read_header(&mut self) -> Result<u32, WebpError> {
+        let mut header = [0u8; 12];
+        match self.webp_reader.read_exact(&mut header) {
+            Ok(()) if &header[..4] == b"RIFF" && &header[8..] == b"WEBP" => {
+                let riff_size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+                riff_size.checked_sub(4).ok_or(WebpError::BadSignature)
+            }
+            Ok(()) => Err(WebpError::BadSignature),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Err(WebpError::BadSignature),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+mod webp_chunk;
+
+use std::io::Read;
+
+pub use webp_chunk::WebpChunk;
+
+use super::WebpError;
+
+/// Iterator of WebpChunk.
+///
+/// Iteration stops at the end of RIFF payload or at the first malformed chunk.
+/// Use `TryWebpChunks` to know why it stopped.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct WebpChunks<R>
+where
+    R: Read,
+{
+    inner: TryWebpChunks<R>,
+}
+
+impl<R> WebpChunks<R>
+where
+    R: Read,
+{
+    /// `payload_len` is the RIFF size excluding "WEBP" fourcc.
+    pub fn new(reader_at_first_chunk: R, payload_len: u32) -> Self {
+        Self {
+            inner: TryWebpChunks::new(reader_at_first_chunk, payload_len),
+        }
+    }
+}
+
+impl<R> Iterator for WebpChunks<R>
+where
+    R: Read,
+{
+    type Item = WebpChunk;
+
+    fn next(&mut self) -> Option<WebpChunk> {
+        self.inner.next()?.ok()
+    }
+}
+
+/// Iterator of `Result<WebpChunk, WebpError>`.
+///
+/// Yields `None` at the end of RIFF payload or after the first error.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TryWebpChunks<R>
+where
+    R: Read,
+{
+    reader_at_next_chunk: R,
+    /// Bytes of RIFF payload not read yet.
+    remaining: u32,
+    finished: bool,
+}
+
+impl<R> TryWebpChunks<R>
+where
+    R: Read,
+{
+    /// `payload_len` is the RIFF size excluding "WEBP" fourcc.
+    pub fn new(reader_at_first_chunk: R, payload_len: u32) -> Self {
+        Self {
+            reader_at_next_chunk: reader_at_first_chunk,
+            remaining: payload_len,
+            finished: false,
+        }
+    }
+}
+
+impl<R> Iterator for TryWebpChunks<R>
+where
+    R: Read,
+{
+    type Item = Result<WebpChunk, WebpError>;
+
+    fn next(&mut self) -> Option<Result<WebpChunk, WebpError>> {
+        if self.finished || self.remaining == 0 {
+            return None;
+        }
+        match WebpChunk::from_reader(&mut self.reader_at_next_chunk, self.remaining) {
+            Ok((chunk, occupied)) => {
+                self.remaining -= occupied;
+                Some(Ok(chunk))
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::{
+    fmt::Display,
+    io::{self, Read},
+};
+
+use super::super::WebpError;
+
+/// Represents a chunk of RIFF container in WebP.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct WebpChunk {
+    fourcc: [u8; 4],
+    data: Vec<u8>,
+}
+
+impl WebpChunk {
+    /// Lossy bitstream.
+    pub const VP8: [u8; 4] = *b"VP8 ";
+    /// Lossless bitstream.
+    pub const VP8L: [u8; 4] = *b"VP8L";
+    /// Extended format header.
+    pub const VP8X: [u8; 4] = *b"VP8X";
+    pub const ALPH: [u8; 4] = *b"ALPH";
+    pub const ANIM: [u8; 4] = *b"ANIM";
+    pub const ANMF: [u8; 4] = *b"ANMF";
+    pub const ICCP: [u8; 4] = *b"ICCP";
+    pub const EXIF: [u8; 4] = *b"EXIF";
+    pub const XMP: [u8; 4] = *b"XMP ";
+
+    /// Chunk identifier such as "VP8X".
+    pub fn fourcc(&self) -> &[u8; 4] {
+        &self.fourcc
+    }
+
+    /// Data without the padding byte.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// # Returns
+    ///
+    /// Chunk and the number of bytes it occupied (header, data and padding).
+    ///
+    /// # Failures
+    ///
+    /// - `WebpError::TruncatedChunk` when `r` reaches EOF in the middle of a chunk.
+    /// - `WebpError::InvalidChunkSize` when the chunk does not fit in `remaining` bytes.
+    pub(super) fn from_reader<R>(r: &mut R, remaining: u32) -> Result<(Self, u32), WebpError>
+    where
+        R: Read,
+    {
+        let mut header = [0u8; 8];
+        Self::read_exact(r, &mut header)?;
+        let fourcc = [header[0], header[1], header[2], header[3]];
+        let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        let padded = size as u64 + size as u64 % 2;
+        let occupied = 8 + padded;
+        if occupied > remaining as u64 {
+            return Err(WebpError::InvalidChunkSize(size));
+        }
+
+        // Not allocating `size` bytes up front: a corrupted size must not exhaust memory.
+        let mut data = Vec::<u8>::new();
+        r.take(padded).read_to_end(&mut data)?;
+        if data.len() as u64 != padded {
+            return Err(WebpError::TruncatedChunk);
+        }
+        data.truncate(size as usize);
+        Ok((Self { fourcc, data }, occupied as u32))
+    }
+
+    fn read_exact<R>(r: &mut R, buf: &mut [u8]) -> Result<(), WebpError>
+    where
+        R: Read,
+    {
+        r.read_exact(buf).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => WebpError::TruncatedChunk,
+            _ => e.into(),
+        })
+    }
+}
+
+impl Display for WebpChunk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Chunk: {}, Data len: {}",
+            self.fourcc.escape_ascii(),
+            self.data.len()
+        )
+    }
+}
@@ -0,0 +1,41 @@
+use std::{error::Error, fmt::Display, io};
+
+/// Errors while reading WebP format.
+#[derive(Debug)]
+pub enum WebpError {
+    /// Leading 12 bytes are not "RIFF", size and "WEBP".
+    BadSignature,
+    /// Input ended before the size given in RIFF header.
+    TruncatedChunk,
+    /// Chunk size exceeds the rest of the RIFF payload.
+    InvalidChunkSize(u32),
+    IoError(io::Error),
+}
+
+impl From<io::Error> for WebpError {
+    fn from(error: io::Error) -> Self {
+        WebpError::IoError(error)
+    }
+}
+
+impl Display for WebpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WebpError::BadSignature => write!(f, "wrong WebP signature"),
+            WebpError::TruncatedChunk => write!(f, "WebP chunk is truncated"),
+            WebpError::InvalidChunkSize(size) => {
+                write!(f, "chunk size exceeds RIFF payload: {}", size)
+            }
+            WebpError::IoError(e) => write!(f, "IO error: {}", e),
+        }
+    }
+}
+
+impl Error for WebpError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WebpError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
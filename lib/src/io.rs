@@ -1,7 +1,21 @@
 mod limited_reader;
+mod multi_reader;
 mod multi_writer;
+mod pipe;
+mod scanner;
 mod section_reader;
+mod shared_multi_writer;
+mod tee_reader;
+mod threaded_writer;
 
 pub use limited_reader::LimitedReader;
-pub use multi_writer::MultiWriter;
+pub use multi_reader::MultiReader;
+pub use multi_writer::{MultiWriter, WritePolicy, WriterFailure, WriterHandle};
+pub use pipe::{pipe, PipeReader, PipeWriter};
+pub use scanner::{
+    split_lines, split_on, split_runes, split_words, ScanError, Scanner, Split, MAX_TOKEN_SIZE,
+};
 pub use section_reader::SectionReader;
+pub use shared_multi_writer::SharedMultiWriter;
+pub use tee_reader::TeeReader;
+pub use threaded_writer::ThreadedWriter;
@@ -1,15 +1,17 @@
-use std::io::{self, Read, Seek};
+use std::{
+    cmp,
+    io::{self, Read},
+};
 
-use super::SectionReader;
-
-/// Reads only limited bytes of given reader from start.
-/// Useful to read header section of a binary file, for example.
+/// Reads only limited bytes of given reader from its current position.
+/// Useful to read header section of a binary file, or to cap a request body read from a socket,
+/// for example.
 ///
 /// # Examples
 ///
 /// ```
 /// use lib::{env::temp_file, io::LimitedReader};
-/// use std::{fs::OpenOptions, io::{self, Read, Write}};
+/// use std::{fs::OpenOptions, io::{self, Read, Seek, SeekFrom, Write}};
 ///
 /// fn main() -> io::Result<()> {
 ///     let mut f = OpenOptions::new()
@@ -18,37 +20,87 @@ use super::SectionReader;
 ///         .read(true)
 ///         .open(temp_file())?;
 ///     writeln!(f, "Example of io.LimitedReader")?;
+///     f.seek(SeekFrom::Start(0))?;
 ///
-///     let mut reader = LimitedReader::new(f, 7)?;
+///     let mut reader = LimitedReader::new(f, 7);
 ///     let mut s = String::new();
 ///     reader.read_to_string(&mut s)?;
 ///     assert_eq!(&s, "Example");
+///     assert_eq!(reader.remaining(), 0);
+///
+///     // the rest is left in the underlying reader
+///     let mut rest = String::new();
+///     reader.into_inner().read_to_string(&mut rest)?;
+///     assert_eq!(&rest, " of io.LimitedReader\n");
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// Works on readers without `Seek`:
+///
+/// ```
+/// use lib::io::LimitedReader;
+/// use std::io::{self, BufRead, Read};
+///
+/// fn main() -> io::Result<()> {
+///     let mut request: &[u8] = b"Content-Length: 5\r\n\r\nhello, world";
+///     let mut header = String::new();
+///     request.read_line(&mut header)?;
+///     request.read_line(&mut String::new())?;
+///
+///     let mut body = String::new();
+///     LimitedReader::new(&mut request, 5).read_to_string(&mut body)?;
+///     assert_eq!(&body, "hello");
 ///
 ///     Ok(())
 /// }
 /// ```
 #[derive(Debug)]
-pub struct LimitedReader<R>(SectionReader<R>)
+pub struct LimitedReader<R>
 where
-    R: Read + Seek;
+    R: Read,
+{
+    reader: R,
+    remaining: u64,
+}
 
 impl<R> LimitedReader<R>
 where
-    R: Read + Seek,
+    R: Read,
 {
-    /// Constructs new LimitedReader that reads bytes
-    /// from start to `min(n_byte, EOF)` from `reader`.
-    pub fn new(reader: R, n_byte: usize) -> io::Result<Self> {
-        let section_reader = SectionReader::new(reader, 0, n_byte)?;
-        Ok(Self(section_reader))
+    /// Constructs new LimitedReader that reads at most `n_byte` bytes
+    /// from the current position of `reader`.
+    pub fn new(reader: R, n_byte: u64) -> Self {
+        Self {
+            reader,
+            remaining: n_byte,
+        }
+    }
+
+    /// Number of bytes that can still be read.
+    /// `reader` may reach EOF before it.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Returns the underlying reader, positioned just after the bytes read so far.
+    pub fn into_inner(self) -> R {
+        self.reader
     }
 }
 
 impl<R> Read for LimitedReader<R>
 where
-    R: Read + Seek,
+    R: Read,
 {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.0.read(buf)
+        if self.remaining == 0 {
+            return Ok(0); // pseudo EOF
+        }
+        let len = cmp::min(buf.len() as u64, self.remaining) as usize;
+        let read_bytes = self.reader.read(&mut buf[..len])?;
+        self.remaining -= read_bytes as u64;
+        Ok(read_bytes)
     }
 }
@@ -0,0 +1,73 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Read},
+};
+
+/// Takes 0, 1, or more `std::io::Read`s and reads them one after another as if concatenated.
+/// `MultiReader` implements `std::io::Read` trait.
+///
+/// Inspired by Go's MultiReader.
+/// Readers are dropped as soon as they reach EOF.
+///
+/// # Examples
+///
+/// ```
+/// use lib::io::MultiReader;
+/// use std::io::{self, Read};
+///
+/// fn main() -> io::Result<()> {
+///     let header: &[u8] = b"--- header ---\n";
+///     let body = io::Cursor::new("body\n");
+///
+///     let mut mr = MultiReader::new(vec![Box::new(header), Box::new(body), Box::new(io::empty())]);
+///     let mut s = String::new();
+///     mr.read_to_string(&mut s)?;
+///     assert_eq!(&s, "--- header ---\nbody\n");
+///
+///     Ok(())
+/// }
+/// ```
+pub struct MultiReader<R = dyn Read>
+where
+    R: Read + ?Sized,
+{
+    readers: VecDeque<Box<R>>,
+}
+
+impl<'a> MultiReader<dyn Read + 'a> {
+    /// Constructor.
+    pub fn new(readers: Vec<Box<dyn Read + 'a>>) -> Self {
+        Self::from_readers(readers)
+    }
+}
+
+impl<R> MultiReader<R>
+where
+    R: Read + ?Sized,
+{
+    /// Constructor for any type of readers, such as `dyn Read + Send`.
+    pub fn from_readers(readers: Vec<Box<R>>) -> Self {
+        Self {
+            readers: readers.into(),
+        }
+    }
+}
+
+impl<R> Read for MultiReader<R>
+where
+    R: Read + ?Sized,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        while let Some(reader) = self.readers.front_mut() {
+            let read_bytes = reader.read(buf)?;
+            if read_bytes > 0 {
+                return Ok(read_bytes);
+            }
+            self.readers.pop_front();
+        }
+        Ok(0)
+    }
+}
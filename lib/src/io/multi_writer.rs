@@ -1,9 +1,26 @@
-use std::io::{self, Write};
+mod write_policy;
+mod writer_failure;
+mod writer_handle;
+
+pub use write_policy::WritePolicy;
+pub use writer_failure::WriterFailure;
+pub use writer_handle::WriterHandle;
+
+use std::{
+    io::{self, Write},
+    mem,
+};
+
+use super::ThreadedWriter;
 
 /// Takes 0, 1, or more `std::io::Write`s and writes to them at a time.
 /// `MultiWriter` implements `std::io::Write` trait.
 ///
 /// Inspired by Go's MultiWriter.
+/// What happens when some of the writers fail is configured by `WritePolicy`.
+///
+/// Writers are `Box<dyn Write>` by default.
+/// `MultiWriter<dyn Write + Send>` can be sent to other threads, and `SharedMultiWriter` shares one among them.
 ///
 /// # Examples
 ///
@@ -23,30 +40,329 @@ use std::io::{self, Write};
 ///     Ok(())
 /// }
 /// ```
-pub struct MultiWriter(Vec<Box<dyn Write>>);
+pub struct MultiWriter<W = dyn Write>
+where
+    W: Write + ?Sized,
+{
+    entries: Vec<Entry<W>>,
+    next_handle: u64,
+    policy: WritePolicy,
+    /// Writers dropped by `WritePolicy::BestEffort`.
+    failures: Vec<WriterFailure>,
+    /// Bytes not written yet by `WritePolicy::AllOrNothing`.
+    pending: Vec<u8>,
+}
+
+struct Entry<W>
+where
+    W: Write + ?Sized,
+{
+    handle: WriterHandle,
+    writer: Box<W>,
+    /// Length of `MultiWriter::pending` already written to `writer`.
+    committed: usize,
+}
 
 impl MultiWriter {
-    /// Constructor.
+    /// Constructor with `WritePolicy::FailFast`.
     pub fn new(writers: Vec<Box<dyn Write>>) -> Self {
-        Self(writers)
+        Self::with_policy(writers, WritePolicy::FailFast)
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::io::{MultiWriter, WritePolicy};
+    /// use std::io::{self, Write};
+    ///
+    /// struct Broken;
+    ///
+    /// impl Write for Broken {
+    ///     fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+    ///         Err(io::Error::new(io::ErrorKind::Other, "broken"))
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut mw = MultiWriter::with_policy(
+    ///         vec![Box::new(Broken), Box::new(io::sink())],
+    ///         WritePolicy::BestEffort,
+    ///     );
+    ///     let broken = mw.handles().next().unwrap();
+    ///     mw.write_all(b"xxx")?;
+    ///     mw.write_all(b"yyy")?;
+    ///
+    ///     let failures = mw.take_failures();
+    ///     assert_eq!(failures.len(), 1);
+    ///     assert_eq!(failures[0].handle, broken);
+    ///     assert_eq!(mw.len(), 1);
+    ///     assert_eq!(failures[0].error.to_string(), "broken");
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_policy(writers: Vec<Box<dyn Write>>, policy: WritePolicy) -> Self {
+        Self::from_writers(writers, policy)
+    }
+
+    /// Constructs new MultiWriter which writes to each of `writers` on its own thread (see `ThreadedWriter`),
+    /// so that a slow writer does not hold up the others.
+    ///
+    /// Errors of a writer are noticed by a later `write()` or `flush()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::io::{MultiWriter, WritePolicy};
+    /// use std::io::{self, Write};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut mw = MultiWriter::threaded(
+    ///         vec![Box::new(io::stdout()), Box::new(io::sink())],
+    ///         WritePolicy::BestEffort,
+    ///     );
+    ///     writeln!(mw, "written in parallel")?;
+    ///     mw.flush()?;
+    ///     assert!(mw.take_failures().is_empty());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn threaded(
+        writers: Vec<Box<dyn Write + Send>>,
+        policy: WritePolicy,
+    ) -> MultiWriter<dyn Write + Send> {
+        let writers = writers
+            .into_iter()
+            .map(|writer| Box::new(ThreadedWriter::new(writer)) as Box<dyn Write + Send>)
+            .collect();
+        MultiWriter::from_writers(writers, policy)
+    }
+}
+
+impl<W> MultiWriter<W>
+where
+    W: Write + ?Sized,
+{
+    /// Constructor for any type of writers, such as `dyn Write + Send`.
+    ///
+    /// Handles of `writers` are returned by `handles()` in this order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::io::{MultiWriter, WritePolicy};
+    /// use std::{io::{self, Write}, thread};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut mw = MultiWriter::<dyn Write + Send>::from_writers(
+    ///         vec![Box::new(io::stdout()), Box::new(io::sink())],
+    ///         WritePolicy::FailFast,
+    ///     );
+    ///     thread::spawn(move || writeln!(mw, "written on another thread"))
+    ///         .join()
+    ///         .unwrap()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_writers(writers: Vec<Box<W>>, policy: WritePolicy) -> Self {
+        let mut multi_writer = Self {
+            entries: vec![],
+            next_handle: 0,
+            policy,
+            failures: vec![],
+            pending: vec![],
+        };
+        for writer in writers {
+            multi_writer.add(writer);
+        }
+        multi_writer
+    }
+
+    /// Adds `writer`, which receives bytes written after this call.
+    ///
+    /// With `WritePolicy::AllOrNothing`, it also receives bytes buffered but not flushed yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::io::MultiWriter;
+    /// use std::io::{self, Write};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut mw = MultiWriter::new(vec![]);
+    ///     mw.write_all(b"nobody receives this")?;
+    ///
+    ///     let stdout = mw.add(Box::new(io::stdout()));
+    ///     writeln!(mw, "printed")?;
+    ///
+    ///     assert!(mw.remove(stdout).is_some());
+    ///     assert!(mw.remove(stdout).is_none());
+    ///     assert!(mw.is_empty());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn add(&mut self, writer: Box<W>) -> WriterHandle {
+        let handle = WriterHandle(self.next_handle);
+        self.next_handle += 1;
+        self.entries.push(Entry {
+            handle,
+            writer,
+            committed: 0,
+        });
+        handle
+    }
+
+    /// Removes the writer of `handle` and returns it,
+    /// or `None` if it has already been removed (or dropped by `WritePolicy::BestEffort`).
+    pub fn remove(&mut self, handle: WriterHandle) -> Option<Box<W>> {
+        let position = self.entries.iter().position(|e| e.handle == handle)?;
+        Some(self.entries.remove(position).writer)
+    }
+
+    /// Handles of the current writers, in the order they were added.
+    pub fn handles(&self) -> impl Iterator<Item = WriterHandle> + '_ {
+        self.entries.iter().map(|e| e.handle)
+    }
+
+    /// Number of the current writers.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn policy(&self) -> WritePolicy {
+        self.policy
+    }
+
+    /// Takes writers dropped by `WritePolicy::BestEffort` so far.
+    pub fn take_failures(&mut self) -> Vec<WriterFailure> {
+        mem::take(&mut self.failures)
+    }
+
+    /// Discards bytes buffered by `WritePolicy::AllOrNothing` and not written by `flush()` yet.
+    ///
+    /// # Returns
+    ///
+    /// `false` if some writers have already received the bytes by a `flush()` which failed on another writer.
+    /// Those bytes cannot be taken back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::io::{MultiWriter, WritePolicy};
+    /// use std::io::{self, Write};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut mw = MultiWriter::with_policy(vec![Box::new(io::stdout())], WritePolicy::AllOrNothing);
+    ///     writeln!(mw, "never printed")?;
+    ///     assert!(mw.rollback());
+    ///
+    ///     writeln!(mw, "printed on flush")?;
+    ///     mw.flush()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn rollback(&mut self) -> bool {
+        self.pending.clear();
+        let mut clean = true;
+        for entry in &mut self.entries {
+            clean &= entry.committed == 0;
+            entry.committed = 0;
+        }
+        clean
+    }
+
+    /// Applies `f` to each writer, dropping ones which fail.
+    ///
+    /// # Failures
+    ///
+    /// When all writers have failed by this call.
+    fn for_each_best_effort<F>(&mut self, mut f: F) -> io::Result<()>
+    where
+        F: FnMut(&mut W) -> io::Result<()>,
+    {
+        if self.entries.is_empty() {
+            return Ok(());
+        }
+        let failures = &mut self.failures;
+        self.entries
+            .retain_mut(|entry| match f(entry.writer.as_mut()) {
+                Ok(()) => true,
+                Err(error) => {
+                    failures.push(WriterFailure {
+                        handle: entry.handle,
+                        error,
+                    });
+                    false
+                }
+            });
+        if self.entries.is_empty() {
+            Err(io::Error::other("all writers failed"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Writes pending bytes to the writers which have not received them yet.
+    /// On failure, a retry resumes from the failed writer.
+    /// A writer which failed in the middle may have received part of the bytes.
+    fn commit(&mut self) -> io::Result<()> {
+        for entry in &mut self.entries {
+            if entry.committed < self.pending.len() {
+                entry.writer.write_all(&self.pending[entry.committed..])?;
+                entry.committed = self.pending.len();
+            }
+            entry.writer.flush()?;
+        }
+        self.pending.clear();
+        for entry in &mut self.entries {
+            entry.committed = 0;
+        }
+        Ok(())
     }
 }
 
-impl Write for MultiWriter {
+impl<W> Write for MultiWriter<W>
+where
+    W: Write + ?Sized,
+{
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        for w in &mut self.0 {
-            // Using `Write::write` is more straightforward but
-            // it sometimes (especially when buf is large) writes only part of buf.
-            // To align bytes written for all writers (self.0), here uses `Write::write_all`.
-            w.write_all(buf)?;
+        match self.policy {
+            WritePolicy::FailFast => {
+                for entry in &mut self.entries {
+                    // Using `Write::write` is more straightforward but
+                    // it sometimes (especially when buf is large) writes only part of buf.
+                    // To align bytes written for all writers, here uses `Write::write_all`.
+                    entry.writer.write_all(buf)?;
+                }
+            }
+            WritePolicy::BestEffort => self.for_each_best_effort(|w| w.write_all(buf))?,
+            WritePolicy::AllOrNothing => self.pending.extend_from_slice(buf),
         }
         Ok(buf.len())
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        for w in &mut self.0 {
-            w.flush()?;
+        match self.policy {
+            WritePolicy::FailFast => {
+                for entry in &mut self.entries {
+                    entry.writer.flush()?;
+                }
+                Ok(())
+            }
+            WritePolicy::BestEffort => self.for_each_best_effort(|w| w.flush()),
+            WritePolicy::AllOrNothing => self.commit(),
         }
-        Ok(())
     }
 }
@@ -0,0 +1,15 @@
+/// How `MultiWriter` handles a writer that fails.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Default)]
+pub enum WritePolicy {
+    /// Returns the first error as is, without writing to the rest of the writers.
+    /// Writers before the failed one have already received the bytes.
+    #[default]
+    FailFast,
+    /// Drops a failed writer and keeps writing to the others.
+    /// Dropped writers are reported by `MultiWriter::take_failures()`,
+    /// and the write fails only when no writer is left.
+    BestEffort,
+    /// Buffers bytes in memory and writes them to all writers on `flush()`.
+    /// Until then, the buffered bytes can be discarded by `MultiWriter::rollback()`.
+    AllOrNothing,
+}
@@ -0,0 +1,22 @@
+use std::{error::Error, fmt::Display, io};
+
+use super::WriterHandle;
+
+/// Writer dropped from `MultiWriter` by `WritePolicy::BestEffort`.
+#[derive(Debug)]
+pub struct WriterFailure {
+    pub handle: WriterHandle,
+    pub error: io::Error,
+}
+
+impl Display for WriterFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "writer #{} failed: {}", self.handle.0, self.error)
+    }
+}
+
+impl Error for WriterFailure {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
@@ -0,0 +1,3 @@
+/// Identifies a writer in `MultiWriter` to remove it or to tell which one failed.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct WriterHandle(pub(super) u64);
@@ -0,0 +1,114 @@
+mod pipe_reader;
+mod pipe_writer;
+
+pub use pipe_reader::PipeReader;
+pub use pipe_writer::PipeWriter;
+
+use std::{
+    io,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+};
+
+/// Creates a synchronous in-memory pipe.
+///
+/// Inspired by Go's io.Pipe.
+/// Each `write()` to `PipeWriter` blocks until `PipeReader` has read all of the bytes,
+/// so no data is buffered beyond a single write.
+/// The reader and the writer can be sent to different threads.
+///
+/// Closing (or dropping) the writer makes the reader reach EOF,
+/// and `PipeWriter::close_with_error()` makes it fail with the given error instead.
+/// Closing (or dropping) the reader makes writes fail with `io::ErrorKind::BrokenPipe`
+/// (or the error given to `PipeReader::close_with_error()`).
+///
+/// # Examples
+///
+/// ```
+/// use lib::io::{pipe, TeeReader};
+/// use std::{io::{self, Read, Write}, thread};
+///
+/// fn main() -> io::Result<()> {
+///     let (reader, mut writer) = pipe();
+///     let producer = thread::spawn(move || -> io::Result<()> {
+///         for i in 0..3 {
+///             writeln!(writer, "line {}", i)?;
+///         }
+///         Ok(())
+///         // dropping `writer` closes the pipe
+///     });
+///
+///     // copies to `uploaded` while keeping a log, without temporary files
+///     let mut uploaded = Vec::new();
+///     let mut tee = TeeReader::new(reader, Vec::new());
+///     tee.read_to_end(&mut uploaded)?;
+///     producer.join().unwrap()?;
+///
+///     let (_, log) = tee.into_inner();
+///     assert_eq!(uploaded, b"line 0\nline 1\nline 2\n");
+///     assert_eq!(log, uploaded);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn pipe() -> (PipeReader, PipeWriter) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State::default()),
+        changed: Condvar::new(),
+    });
+    (PipeReader::new(shared.clone()), PipeWriter::new(shared))
+}
+
+/// State shared by both ends of a pipe.
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    /// Notified whenever `state` changes.
+    changed: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn wait<'s>(&self, state: MutexGuard<'s, State>) -> MutexGuard<'s, State> {
+        self.changed.wait(state).unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[derive(Default, Debug)]
+struct State {
+    /// Bytes of the current write.
+    data: Vec<u8>,
+    /// Bytes of `data` already read.
+    read: usize,
+    reader_closed: Option<Closed>,
+    writer_closed: Option<Closed>,
+}
+
+impl State {
+    fn unread(&self) -> &[u8] {
+        &self.data[self.read..]
+    }
+}
+
+/// How an end of a pipe was closed.
+#[derive(Debug)]
+enum Closed {
+    Normally,
+    /// `io::Error` is not `Clone`, so its parts are kept to be returned repeatedly.
+    WithError(io::ErrorKind, String),
+}
+
+impl Closed {
+    fn with_error(error: io::Error) -> Self {
+        Closed::WithError(error.kind(), error.to_string())
+    }
+
+    fn to_error(&self) -> Option<io::Error> {
+        match self {
+            Closed::Normally => None,
+            Closed::WithError(kind, message) => Some(io::Error::new(*kind, message.clone())),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::{
+    cmp,
+    io::{self, Read},
+    sync::Arc,
+};
+
+use super::{Closed, Shared};
+
+/// Read end of `pipe()`.
+#[derive(Debug)]
+pub struct PipeReader {
+    shared: Arc<Shared>,
+}
+
+impl PipeReader {
+    pub(super) fn new(shared: Arc<Shared>) -> Self {
+        Self { shared }
+    }
+
+    /// Closes the read end. Subsequent and blocked writes fail with `io::ErrorKind::BrokenPipe`.
+    pub fn close(&mut self) {
+        self.close_as(Closed::Normally);
+    }
+
+    /// Closes the read end. Subsequent and blocked writes fail with `error`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::io::pipe;
+    /// use std::io::{self, Write};
+    ///
+    /// let (mut reader, mut writer) = pipe();
+    /// reader.close_with_error(io::Error::new(io::ErrorKind::InvalidData, "checksum mismatch"));
+    ///
+    /// let err = writer.write(b"data").unwrap_err();
+    /// assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    /// assert_eq!(err.to_string(), "checksum mismatch");
+    /// ```
+    pub fn close_with_error(&mut self, error: io::Error) {
+        self.close_as(Closed::with_error(error));
+    }
+
+    fn close_as(&mut self, closed: Closed) {
+        let mut state = self.shared.lock();
+        if state.reader_closed.is_none() {
+            state.reader_closed = Some(closed);
+            self.shared.changed.notify_all();
+        }
+    }
+}
+
+impl Read for PipeReader {
+    /// Blocks until the writer writes or closes.
+    ///
+    /// # Failures
+    ///
+    /// - The error given to `PipeWriter::close_with_error()`.
+    /// - `io::ErrorKind::BrokenPipe` when the read end is closed.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut state = self.shared.lock();
+        loop {
+            if state.reader_closed.is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    "read from closed pipe",
+                ));
+            }
+            if buf.is_empty() {
+                return Ok(0);
+            }
+            if !state.unread().is_empty() {
+                let read_bytes = cmp::min(buf.len(), state.unread().len());
+                buf[..read_bytes].copy_from_slice(&state.unread()[..read_bytes]);
+                state.read += read_bytes;
+                if state.unread().is_empty() {
+                    // lets the writer return
+                    self.shared.changed.notify_all();
+                }
+                return Ok(read_bytes);
+            }
+            if let Some(closed) = &state.writer_closed {
+                return match closed.to_error() {
+                    Some(e) => Err(e),
+                    None => Ok(0),
+                };
+            }
+            state = self.shared.wait(state);
+        }
+    }
+}
+
+impl Drop for PipeReader {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
@@ -0,0 +1,108 @@
+use std::{
+    io::{self, Write},
+    sync::Arc,
+};
+
+use super::{Closed, Shared};
+
+/// Write end of `pipe()`.
+#[derive(Debug)]
+pub struct PipeWriter {
+    shared: Arc<Shared>,
+}
+
+impl PipeWriter {
+    pub(super) fn new(shared: Arc<Shared>) -> Self {
+        Self { shared }
+    }
+
+    /// Closes the write end. The reader reaches EOF after reading bytes already written.
+    pub fn close(&mut self) {
+        self.close_as(Closed::Normally);
+    }
+
+    /// Closes the write end. The reader fails with `error` after reading bytes already written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::io::pipe;
+    /// use std::{io::{self, Read}, thread};
+    ///
+    /// let (mut reader, mut writer) = pipe();
+    /// thread::spawn(move || {
+    ///     writer.close_with_error(io::Error::new(io::ErrorKind::ConnectionReset, "upstream lost"));
+    /// });
+    ///
+    /// let err = reader.read_to_end(&mut vec![]).unwrap_err();
+    /// assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+    /// ```
+    pub fn close_with_error(&mut self, error: io::Error) {
+        self.close_as(Closed::with_error(error));
+    }
+
+    fn close_as(&mut self, closed: Closed) {
+        let mut state = self.shared.lock();
+        if state.writer_closed.is_none() {
+            state.writer_closed = Some(closed);
+            self.shared.changed.notify_all();
+        }
+    }
+}
+
+impl Write for PipeWriter {
+    /// Blocks until the reader reads all of `buf` or closes.
+    ///
+    /// # Failures
+    ///
+    /// - The error given to `PipeReader::close_with_error()`.
+    /// - `io::ErrorKind::BrokenPipe` when either end is closed.
+    ///   Bytes read before the reader closed are reported as written instead.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.shared.lock();
+        if state.writer_closed.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "write to closed pipe",
+            ));
+        }
+        if let Some(closed) = &state.reader_closed {
+            return Err(reader_closed_error(closed));
+        }
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        state.data.clear();
+        state.data.extend_from_slice(buf);
+        state.read = 0;
+        self.shared.changed.notify_all();
+
+        while !state.unread().is_empty() && state.reader_closed.is_none() {
+            state = self.shared.wait(state);
+        }
+        let written = state.read;
+        state.data.clear();
+        state.read = 0;
+        match &state.reader_closed {
+            Some(closed) if written == 0 => Err(reader_closed_error(closed)),
+            _ => Ok(written),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for PipeWriter {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+fn reader_closed_error(closed: &Closed) -> io::Error {
+    closed
+        .to_error()
+        .unwrap_or_else(|| io::Error::new(io::ErrorKind::BrokenPipe, "read end of pipe is closed"))
+}
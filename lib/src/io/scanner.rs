@@ -0,0 +1,229 @@
+mod scan_error;
+mod split;
+
+pub use scan_error::ScanError;
+pub use split::{split_lines, split_on, split_runes, split_words, Split};
+
+use std::{
+    cmp,
+    io::{self, Read},
+    ops::Range,
+    str,
+};
+
+/// Buffer size allocated first.
+const INITIAL_BUF_LEN: usize = 4096;
+
+/// Default maximum token size, same as Go's bufio.MaxScanTokenSize.
+pub const MAX_TOKEN_SIZE: usize = 64 * 1024;
+
+/// Reads tokens (lines by default) from given reader.
+///
+/// Inspired by Go's bufio.Scanner.
+/// How to split the input is given as a split function (see `Split`):
+/// `split_lines`, `split_words`, `split_runes`, `split_on(delimiter)` or a closure.
+///
+/// Tokens are borrowed from the internal buffer instead of being allocated,
+/// so they are valid until the next scan.
+///
+/// # Examples
+///
+/// ```
+/// use lib::io::{Scanner, ScanError, Split};
+///
+/// fn main() -> Result<(), ScanError> {
+///     let mut scanner = Scanner::new("1行目\r\n2行目\n3行目".as_bytes());
+///     let mut n_lines = 0;
+///     while let Some(line) = scanner.scan_str()? {
+///         n_lines += 1;
+///         assert!(line.ends_with("行目"));
+///     }
+///     assert_eq!(n_lines, 3);
+///
+///     // custom split function: comma-separated values, trimmed
+///     let split_commas = |data: &[u8], at_eof: bool| {
+///         let end = match data.iter().position(|&b| b == b',') {
+///             Some(i) => i,
+///             None if at_eof && !data.is_empty() => data.len(),
+///             None => return None,
+///         };
+///         let start = data[..end].iter().take_while(|b| b.is_ascii_whitespace()).count();
+///         Some(Split { advance: (end + 1).min(data.len()), token: Some(start..end) })
+///     };
+///     let mut scanner = Scanner::with_split(&b"a, b,  c"[..], split_commas);
+///     assert_eq!(scanner.scan()?, Some(&b"a"[..]));
+///     assert_eq!(scanner.scan()?, Some(&b"b"[..]));
+///     assert_eq!(scanner.scan()?, Some(&b"c"[..]));
+///     assert_eq!(scanner.scan()?, None);
+///
+///     Ok(())
+/// }
+/// ```
+pub struct Scanner<R, S = fn(&[u8], bool) -> Option<Split>>
+where
+    R: Read,
+    S: FnMut(&[u8], bool) -> Option<Split>,
+{
+    reader: R,
+    split: S,
+    /// Bytes read: `buf[start..end]` is not consumed yet.
+    buf: Vec<u8>,
+    start: usize,
+    end: usize,
+    max_token_size: usize,
+    at_eof: bool,
+    /// Whether an error has stopped scanning.
+    failed: bool,
+}
+
+impl<R> Scanner<R>
+where
+    R: Read,
+{
+    /// Constructs new Scanner which splits into lines by `split_lines`.
+    pub fn new(reader: R) -> Self {
+        Self::with_split(reader, split_lines)
+    }
+}
+
+impl<R, S> Scanner<R, S>
+where
+    R: Read,
+    S: FnMut(&[u8], bool) -> Option<Split>,
+{
+    /// Constructs new Scanner which splits by `split`.
+    pub fn with_split(reader: R, split: S) -> Self {
+        Self {
+            reader,
+            split,
+            buf: vec![],
+            start: 0,
+            end: 0,
+            max_token_size: MAX_TOKEN_SIZE,
+            at_eof: false,
+            failed: false,
+        }
+    }
+
+    /// Changes the maximum token size (`MAX_TOKEN_SIZE` by default).
+    /// A token and its delimiter must fit in it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::io::{Scanner, ScanError};
+    ///
+    /// let mut scanner = Scanner::new(&b"short\ntoo long line\n"[..]).with_max_token_size(8);
+    /// assert_eq!(scanner.scan().unwrap(), Some(&b"short"[..]));
+    /// assert!(matches!(scanner.scan(), Err(ScanError::TokenTooLong(8))));
+    /// // stops after an error
+    /// assert_eq!(scanner.scan().unwrap(), None);
+    /// ```
+    pub fn with_max_token_size(mut self, max_token_size: usize) -> Self {
+        self.max_token_size = max_token_size;
+        self
+    }
+
+    /// Reads the next token.
+    ///
+    /// # Returns
+    ///
+    /// The token, or `None` at the end of input (or after an error).
+    ///
+    /// # Failures
+    ///
+    /// - `ScanError::TokenTooLong` when a token does not fit in the maximum token size.
+    /// - `ScanError::IoError` from the reader.
+    ///
+    /// # Panics
+    ///
+    /// When the split function returns a position out of `data`.
+    pub fn scan(&mut self) -> Result<Option<&[u8]>, ScanError> {
+        if self.failed {
+            return Ok(None);
+        }
+        match self.next_token() {
+            Ok(Some(range)) => Ok(Some(&self.buf[range])),
+            Ok(None) => Ok(None),
+            Err(e) => {
+                self.failed = true;
+                Err(e)
+            }
+        }
+    }
+
+    /// Reads the next token as `&str`.
+    ///
+    /// # Failures
+    ///
+    /// In addition to those of `scan()`, `ScanError::InvalidUtf8` when the token is not UTF-8.
+    /// Scanning can continue after this error.
+    pub fn scan_str(&mut self) -> Result<Option<&str>, ScanError> {
+        match self.scan()? {
+            Some(token) => Ok(Some(str::from_utf8(token)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the underlying reader. Bytes read but not consumed yet are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Position of the next token in `buf`.
+    fn next_token(&mut self) -> Result<Option<Range<usize>>, ScanError> {
+        loop {
+            if self.end > self.start || self.at_eof {
+                let data = &self.buf[self.start..self.end];
+                if let Some(Split { advance, token }) = (self.split)(data, self.at_eof) {
+                    assert!(
+                        advance <= data.len()
+                            && token.as_ref().is_none_or(|token| token.end <= data.len()),
+                        "split function returned out of range"
+                    );
+                    let start = self.start;
+                    self.start += advance;
+                    match token {
+                        Some(token) => return Ok(Some(start + token.start..start + token.end)),
+                        // consumed bytes without a token: splits the rest
+                        None if advance > 0 => continue,
+                        None => {}
+                    }
+                }
+            }
+            if self.at_eof {
+                return Ok(None);
+            }
+            self.fill_buf()?;
+        }
+    }
+
+    /// Reads more bytes into `buf`, making room for them.
+    fn fill_buf(&mut self) -> Result<(), ScanError> {
+        if self.start > 0 {
+            self.buf.copy_within(self.start..self.end, 0);
+            self.end -= self.start;
+            self.start = 0;
+        }
+        if self.end == self.buf.len() {
+            if self.buf.len() >= self.max_token_size {
+                return Err(ScanError::TokenTooLong(self.max_token_size));
+            }
+            let len = cmp::max(INITIAL_BUF_LEN, self.buf.len() * 2);
+            self.buf.resize(cmp::min(len, self.max_token_size), 0);
+        }
+        let read_bytes = loop {
+            match self.reader.read(&mut self.buf[self.end..]) {
+                Ok(n) => break n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        };
+        if read_bytes == 0 {
+            self.at_eof = true;
+        } else {
+            self.end += read_bytes;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use std::{error::Error, fmt::Display, io, str::Utf8Error};
+
+/// Errors while scanning tokens.
+#[derive(Debug)]
+pub enum ScanError {
+    /// Token (with its delimiter) does not fit in the maximum token size, which is held.
+    TokenTooLong(usize),
+    /// Token is not valid UTF-8 (only by `Scanner::scan_str()`).
+    InvalidUtf8(Utf8Error),
+    IoError(io::Error),
+}
+
+impl From<io::Error> for ScanError {
+    fn from(error: io::Error) -> Self {
+        ScanError::IoError(error)
+    }
+}
+
+impl From<Utf8Error> for ScanError {
+    fn from(error: Utf8Error) -> Self {
+        ScanError::InvalidUtf8(error)
+    }
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScanError::TokenTooLong(max) => write!(f, "token exceeds {} bytes", max),
+            ScanError::InvalidUtf8(e) => write!(f, "token is not UTF-8: {}", e),
+            ScanError::IoError(e) => write!(f, "IO error: {}", e),
+        }
+    }
+}
+
+impl Error for ScanError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ScanError::InvalidUtf8(e) => Some(e),
+            ScanError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use std::{cmp, ops::Range, str};
+
+/// Token found by a split function.
+///
+/// A split function is called as `split(data, at_eof)` with the bytes not consumed yet
+/// and whether the reader has reached EOF.
+/// It returns `None` to request more data, or at EOF when no token is left.
+/// It can also consume bytes without a token (such as separators before a token),
+/// after which it is called again with the rest.
+///
+/// # Examples
+///
+/// ```
+/// use lib::io::{split_lines, Scanner, ScanError, Split};
+///
+/// fn main() -> Result<(), ScanError> {
+///     // lines, skipping empty ones
+///     let split_non_empty_lines = |data: &[u8], at_eof: bool| {
+///         let split = split_lines(data, at_eof)?;
+///         match split.token {
+///             Some(ref token) if token.is_empty() => Some(Split::skip(split.advance)),
+///             _ => Some(split),
+///         }
+///     };
+///     let mut scanner = Scanner::with_split(&b"a\n\n\r\nb\n\n"[..], split_non_empty_lines);
+///     assert_eq!(scanner.scan()?, Some(&b"a"[..]));
+///     assert_eq!(scanner.scan()?, Some(&b"b"[..]));
+///     assert_eq!(scanner.scan()?, None);
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Split {
+    /// Number of bytes consumed, including delimiters.
+    pub advance: usize,
+    /// Position of the token in `data`, or `None` to only consume `advance` bytes.
+    /// Consuming 0 bytes without a token is the same as requesting more data.
+    pub token: Option<Range<usize>>,
+}
+
+impl Split {
+    /// Token at the start of `data` consuming `advance` bytes in total.
+    pub fn new(advance: usize, token_len: usize) -> Self {
+        Self {
+            advance,
+            token: Some(0..token_len),
+        }
+    }
+
+    /// Consumes `advance` bytes without a token.
+    pub fn skip(advance: usize) -> Self {
+        Self {
+            advance,
+            token: None,
+        }
+    }
+}
+
+/// Splits into lines terminated by LF, CRLF or CR, without the terminators.
+/// The last line may lack a terminator.
+///
+/// # Examples
+///
+/// ```
+/// use lib::io::{split_lines, Scanner, ScanError};
+///
+/// fn main() -> Result<(), ScanError> {
+///     let mut scanner = Scanner::with_split(&b"unix\nwindows\r\nmac\rlast"[..], split_lines);
+///     let mut lines = vec![];
+///     while let Some(line) = scanner.scan_str()? {
+///         lines.push(line.to_string());
+///     }
+///     assert_eq!(lines, ["unix", "windows", "mac", "last"]);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn split_lines(data: &[u8], at_eof: bool) -> Option<Split> {
+    match data.iter().position(|&b| b == b'\n' || b == b'\r') {
+        Some(i) if data[i] == b'\n' => Some(Split::new(i + 1, i)),
+        Some(i) => match data.get(i + 1) {
+            Some(b'\n') => Some(Split::new(i + 2, i)),
+            Some(_) => Some(Split::new(i + 1, i)),
+            // LF may follow in the next read
+            None if at_eof => Some(Split::new(i + 1, i)),
+            None => None,
+        },
+        None if at_eof && !data.is_empty() => Some(Split::new(data.len(), data.len())),
+        None => None,
+    }
+}
+
+/// Splits into words separated by Unicode whitespace.
+/// Whitespace is consumed as it is read, so runs of it may be longer than the maximum token size.
+///
+/// # Examples
+///
+/// ```
+/// use lib::io::{split_words, Scanner, ScanError};
+///
+/// fn main() -> Result<(), ScanError> {
+///     // includes an ideographic space (U+3000)
+///     let mut scanner = Scanner::with_split("  hello,\tworld\n 全角\u{3000}スペース ".as_bytes(), split_words);
+///     let mut words = vec![];
+///     while let Some(word) = scanner.scan_str()? {
+///         words.push(word.to_string());
+///     }
+///     assert_eq!(words, ["hello,", "world", "全角", "スペース"]);
+///
+///     let spaces = " ".repeat(100_000) + "word";
+///     let mut scanner = Scanner::with_split(spaces.as_bytes(), split_words);
+///     assert_eq!(scanner.scan_str()?, Some("word"));
+///     assert_eq!(scanner.scan_str()?, None);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn split_words(data: &[u8], at_eof: bool) -> Option<Split> {
+    let mut start = 0;
+    loop {
+        match first_char(&data[start..], at_eof) {
+            Some((Some(c), len)) if c.is_whitespace() => start += len,
+            Some(_) => break,
+            // consumes whitespace read so far, not to keep it in the buffer
+            None if start > 0 => return Some(Split::skip(start)),
+            None => return None,
+        }
+    }
+    let mut end = start;
+    loop {
+        match first_char(&data[end..], at_eof) {
+            Some((Some(c), len)) if c.is_whitespace() => {
+                return Some(Split {
+                    advance: end + len,
+                    token: Some(start..end),
+                })
+            }
+            Some((_, len)) => end += len,
+            None if at_eof => {
+                return Some(Split {
+                    advance: end,
+                    token: Some(start..end),
+                })
+            }
+            // the word continues in the next read
+            None if start > 0 => return Some(Split::skip(start)),
+            None => return None,
+        }
+    }
+}
+
+/// Splits into UTF-8 encoded characters.
+/// Each invalid byte sequence is a token by itself.
+///
+/// # Examples
+///
+/// ```
+/// use lib::io::{split_runes, Scanner, ScanError};
+///
+/// fn main() -> Result<(), ScanError> {
+///     let mut scanner = Scanner::with_split(&b"a\xE3\x81\x82\xFF"[..], split_runes);
+///     assert_eq!(scanner.scan()?, Some(&b"a"[..]));
+///     assert_eq!(scanner.scan_str()?, Some("あ"));
+///     assert_eq!(scanner.scan()?, Some(&b"\xFF"[..]));
+///     assert_eq!(scanner.scan()?, None);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn split_runes(data: &[u8], at_eof: bool) -> Option<Split> {
+    first_char(data, at_eof).map(|(_, len)| Split::new(len, len))
+}
+
+/// Returns a split function which splits at each `delimiter`, excluding it.
+/// The last token may lack a delimiter.
+///
+/// # Examples
+///
+/// ```
+/// use lib::io::{split_on, Scanner, ScanError};
+///
+/// fn main() -> Result<(), ScanError> {
+///     // output of `find . -print0`
+///     let mut scanner = Scanner::with_split(&b"./a b\0./c\nd\0"[..], split_on(b'\0'));
+///     assert_eq!(scanner.scan()?, Some(&b"./a b"[..]));
+///     assert_eq!(scanner.scan()?, Some(&b"./c\nd"[..]));
+///     assert_eq!(scanner.scan()?, None);
+///
+///     Ok(())
+/// }
+/// ```
+pub fn split_on(delimiter: u8) -> impl FnMut(&[u8], bool) -> Option<Split> {
+    move |data, at_eof| match data.iter().position(|&b| b == delimiter) {
+        Some(i) => Some(Split::new(i + 1, i)),
+        None if at_eof && !data.is_empty() => Some(Split::new(data.len(), data.len())),
+        None => None,
+    }
+}
+
+/// Decodes the first character of `data`.
+///
+/// # Returns
+///
+/// - `Some((Some(c), len))` for a valid character of `len` bytes.
+/// - `Some((None, len))` for an invalid sequence of `len` bytes.
+/// - `None` when `data` is empty, or ends in the middle of a character before EOF.
+fn first_char(data: &[u8], at_eof: bool) -> Option<(Option<char>, usize)> {
+    let prefix = &data[..cmp::min(4, data.len())];
+    let valid = match str::from_utf8(prefix) {
+        Ok(s) => s,
+        Err(e) if e.valid_up_to() > 0 => {
+            str::from_utf8(&prefix[..e.valid_up_to()]).expect("validated")
+        }
+        Err(e) => {
+            return match e.error_len() {
+                Some(len) => Some((None, len)),
+                None if at_eof => Some((None, prefix.len())),
+                None => None,
+            }
+        }
+    };
+    valid.chars().next().map(|c| (Some(c), c.len_utf8()))
+}
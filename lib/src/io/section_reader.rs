@@ -1,13 +1,18 @@
-use std::io::{self, Read, Seek, SeekFrom};
+use std::{
+    cmp,
+    io::{self, Read, Seek, SeekFrom},
+};
 
 /// Reads only limited section (bytes window) of given reader.
 /// Useful to read part of a binary file, for example.
 ///
+/// Positions of `Seek` and `read_at()` are relative to the start of the section.
+///
 /// # Examples
 ///
 /// ```
 /// use lib::{env::temp_file, io::SectionReader};
-/// use std::{fs::OpenOptions, io::{self, Read, Write}};
+/// use std::{fs::OpenOptions, io::{self, Read, Seek, SeekFrom, Write}};
 ///
 /// fn main() -> io::Result<()> {
 ///     let mut f = OpenOptions::new()
@@ -22,6 +27,16 @@ use std::io::{self, Read, Seek, SeekFrom};
 ///     reader.read_to_string(&mut s)?;
 ///     assert_eq!(&s, "Section");
 ///
+///     // random access inside the section
+///     assert_eq!(reader.seek(SeekFrom::End(-3))?, 4);
+///     let mut buf = [0u8; 3];
+///     reader.read_exact(&mut buf)?;
+///     assert_eq!(&buf, b"ion");
+///
+///     // seeking is clamped to the section
+///     assert_eq!(reader.seek(SeekFrom::Current(-100))?, 0);
+///     assert_eq!(reader.seek(SeekFrom::Start(100))?, reader.size());
+///
 ///     Ok(())
 /// }
 /// ```
@@ -30,8 +45,13 @@ pub struct SectionReader<R>
 where
     R: Read + Seek,
 {
-    seeked_reader: R,
-    reads_upto: usize,
+    reader: R,
+    /// Start of the section in `reader`.
+    base: u64,
+    /// End of the section in `reader`.
+    limit: u64,
+    /// Current position in `reader`, in `[base, limit]`.
+    pos: u64,
 }
 
 impl<R> SectionReader<R>
@@ -43,10 +63,63 @@ where
     pub fn new(mut reader: R, offset_byte: u64, n_byte: usize) -> io::Result<Self> {
         reader.seek(SeekFrom::Start(offset_byte))?;
         Ok(Self {
-            seeked_reader: reader,
-            reads_upto: n_byte,
+            reader,
+            base: offset_byte,
+            limit: offset_byte.saturating_add(n_byte as u64),
+            pos: offset_byte,
         })
     }
+
+    /// Size of the section in bytes, which is `n_byte` given to `new()`
+    /// even if `reader` ends before the end of the section.
+    pub fn size(&self) -> u64 {
+        self.limit - self.base
+    }
+
+    /// Reads bytes from `offset` (relative to the start of the section) into `buf`
+    /// without changing the position used by `Read`.
+    ///
+    /// Unlike `read()`, fills `buf` as long as the section and `reader` have data.
+    ///
+    /// # Returns
+    ///
+    /// Number of bytes read, which is less than `buf.len()` only at the end of the section
+    /// (or of `reader`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib::io::SectionReader;
+    /// use std::io::{self, Cursor, Read};
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut reader = SectionReader::new(Cursor::new("Example of io.SectionReader"), 14, 7)?;
+    ///     let mut buf = [0u8; 4];
+    ///     assert_eq!(reader.read_at(&mut buf, 4)?, 3);
+    ///     assert_eq!(&buf[..3], b"ion");
+    ///     assert_eq!(reader.read_at(&mut buf, 7)?, 0);
+    ///
+    ///     // reading still starts from the beginning
+    ///     let mut s = String::new();
+    ///     reader.read_to_string(&mut s)?;
+    ///     assert_eq!(&s, "Section");
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn read_at(&mut self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        let start = match self.base.checked_add(offset) {
+            Some(start) if start < self.limit => start,
+            _ => return Ok(0),
+        };
+        let len = cmp::min(buf.len() as u64, self.limit - start) as usize;
+
+        self.reader.seek(SeekFrom::Start(start))?;
+        let result = read_full(&mut self.reader, &mut buf[..len]);
+        // restore even if reading failed
+        self.reader.seek(SeekFrom::Start(self.pos))?;
+        result
+    }
 }
 
 impl<R> Read for SectionReader<R>
@@ -54,22 +127,50 @@ where
     R: Read + Seek,
 {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        if self.reads_upto == 0 {
-            Ok(0) // pseudo EOF
-        } else {
-            let read_byes = self.seeked_reader.read(buf)?;
-            if read_byes == 0 {
-                // got EOF
-                Ok(0)
-            } else if read_byes <= self.reads_upto {
-                self.reads_upto -= read_byes;
-                Ok(read_byes)
-            } else {
-                // actually read `read_bytes` but pretend to did just `reads_upto` bytes.
-                let ret = self.reads_upto;
-                self.reads_upto = 0;
-                Ok(ret)
-            }
+        if self.pos >= self.limit {
+            return Ok(0); // pseudo EOF
+        }
+        let len = cmp::min(buf.len() as u64, self.limit - self.pos) as usize;
+        let read_bytes = self.reader.read(&mut buf[..len])?;
+        self.pos += read_bytes as u64;
+        Ok(read_bytes)
+    }
+}
+
+impl<R> Seek for SectionReader<R>
+where
+    R: Read + Seek,
+{
+    /// Seeks relative to the section: `SeekFrom::Start(0)` is its first byte and
+    /// `SeekFrom::End(0)` is just after its last byte.
+    /// The new position is clamped to the section instead of failing.
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (origin, offset) = match pos {
+            SeekFrom::Start(offset) => (self.base, offset as i128),
+            SeekFrom::End(offset) => (self.limit, offset as i128),
+            SeekFrom::Current(offset) => (self.pos, offset as i128),
+        };
+        let pos = (origin as i128 + offset).clamp(self.base as i128, self.limit as i128) as u64;
+
+        self.reader.seek(SeekFrom::Start(pos))?;
+        self.pos = pos;
+        Ok(pos - self.base)
+    }
+}
+
+/// Reads into `buf` until it is full or `reader` reaches EOF.
+fn read_full<R>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize>
+where
+    R: Read,
+{
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
         }
     }
+    Ok(filled)
 }
@@ -0,0 +1,112 @@
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use super::{MultiWriter, WritePolicy, WriterFailure, WriterHandle};
+
+/// `MultiWriter` shared among threads.
+/// Clones refer to the same writers, so that writers can be added and removed while others write.
+///
+/// Each `write()` goes to all writers before another one starts.
+/// A slow writer holds up all threads; wrap it in `ThreadedWriter` to avoid that.
+///
+/// # Examples
+///
+/// Broadcasting logs to subscribers which come and go:
+///
+/// ```
+/// use lib::io::{SharedMultiWriter, WritePolicy};
+/// use std::{
+///     io::{self, Write},
+///     sync::{Arc, Mutex},
+///     thread,
+/// };
+///
+/// #[derive(Clone, Default)]
+/// struct Subscriber(Arc<Mutex<Vec<u8>>>);
+///
+/// impl Write for Subscriber {
+///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+///         self.0.lock().unwrap().write(buf)
+///     }
+///
+///     fn flush(&mut self) -> io::Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// fn main() -> io::Result<()> {
+///     let hub = SharedMultiWriter::new(WritePolicy::BestEffort);
+///     let subscriber = Subscriber::default();
+///     let handle = hub.add(Box::new(subscriber.clone()));
+///
+///     let logger = {
+///         let mut hub = hub.clone();
+///         thread::spawn(move || writeln!(hub, "hello"))
+///     };
+///     logger.join().unwrap()?;
+///
+///     hub.remove(handle);
+///     writeln!(hub.clone(), "nobody listens")?;
+///     assert_eq!(subscriber.0.lock().unwrap().as_slice(), b"hello\n");
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SharedMultiWriter(Arc<Mutex<MultiWriter<dyn Write + Send>>>);
+
+impl SharedMultiWriter {
+    /// Constructs new SharedMultiWriter without writers.
+    pub fn new(policy: WritePolicy) -> Self {
+        Self(Arc::new(Mutex::new(MultiWriter::from_writers(
+            vec![],
+            policy,
+        ))))
+    }
+
+    /// See `MultiWriter::add()`.
+    pub fn add(&self, writer: Box<dyn Write + Send>) -> WriterHandle {
+        self.lock().add(writer)
+    }
+
+    /// See `MultiWriter::remove()`.
+    pub fn remove(&self, handle: WriterHandle) -> Option<Box<dyn Write + Send>> {
+        self.lock().remove(handle)
+    }
+
+    /// Number of the current writers.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// See `MultiWriter::take_failures()`.
+    pub fn take_failures(&self) -> Vec<WriterFailure> {
+        self.lock().take_failures()
+    }
+
+    /// See `MultiWriter::rollback()`.
+    pub fn rollback(&self) -> bool {
+        self.lock().rollback()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MultiWriter<dyn Write + Send>> {
+        // writers stay usable even if a thread panicked while writing
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Write for SharedMultiWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.lock().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.lock().flush()
+    }
+}
@@ -0,0 +1,62 @@
+use std::io::{self, Read, Write};
+
+/// Writes to given writer what is read from given reader.
+/// `TeeReader` implements `std::io::Read` trait.
+///
+/// Inspired by Go's TeeReader.
+/// An error from the writer is returned as a read error.
+///
+/// # Examples
+///
+/// ```
+/// use lib::io::TeeReader;
+/// use std::io::{self, Read};
+///
+/// fn main() -> io::Result<()> {
+///     let mut tee = TeeReader::new(&b"uploaded and logged"[..], Vec::new());
+///     // consumes all, as an upload would
+///     io::copy(&mut tee, &mut io::sink())?;
+///
+///     let (_, log) = tee.into_inner();
+///     assert_eq!(log, b"uploaded and logged");
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct TeeReader<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    reader: R,
+    writer: W,
+}
+
+impl<R, W> TeeReader<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    /// Constructor.
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+
+    /// Returns the underlying reader and writer.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+}
+
+impl<R, W> Read for TeeReader<R, W>
+where
+    R: Read,
+    W: Write,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read_bytes = self.reader.read(buf)?;
+        self.writer.write_all(&buf[..read_bytes])?;
+        Ok(read_bytes)
+    }
+}
@@ -0,0 +1,139 @@
+use std::{
+    io::{self, Write},
+    sync::mpsc::{self, Receiver, SyncSender},
+    thread::{self, JoinHandle},
+};
+
+/// Default number of buffers queued before `write()` blocks.
+const QUEUE_LEN: usize = 64;
+
+enum Message {
+    Data(Vec<u8>),
+    /// Flushes the writer and sends back the result (or an earlier error).
+    Flush(SyncSender<io::Result<()>>),
+}
+
+/// Writes to given writer on a background thread, so that a slow writer does not block the caller
+/// until its queue is full.
+///
+/// Since `write()` returns as soon as bytes are queued, an error of the underlying writer is returned
+/// by a later `write()` or `flush()`, after which all writes fail.
+/// Dropping it waits for the queued bytes to be written.
+///
+/// # Examples
+///
+/// ```
+/// use lib::io::ThreadedWriter;
+/// use std::io::{self, Write};
+///
+/// fn main() -> io::Result<()> {
+///     let mut writer = ThreadedWriter::new(io::sink());
+///     writer.write_all(b"written on another thread")?;
+///     writer.flush()?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ThreadedWriter {
+    sender: Option<SyncSender<Message>>,
+    worker: Option<JoinHandle<()>>,
+    /// Errors from the background thread.
+    errors: Receiver<io::Error>,
+    failed: bool,
+}
+
+impl ThreadedWriter {
+    pub fn new<W>(writer: W) -> Self
+    where
+        W: Write + Send + 'static,
+    {
+        Self::with_queue_len(writer, QUEUE_LEN)
+    }
+
+    /// Constructs new ThreadedWriter whose `write()` blocks while `queue_len` buffers are waiting.
+    pub fn with_queue_len<W>(mut writer: W, queue_len: usize) -> Self
+    where
+        W: Write + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::sync_channel::<Message>(queue_len);
+        let (error_sender, errors) = mpsc::channel();
+        let worker = thread::spawn(move || {
+            let mut failed = false;
+            for message in receiver {
+                match message {
+                    Message::Data(_) if failed => {}
+                    Message::Data(buf) => {
+                        if let Err(e) = writer.write_all(&buf) {
+                            failed = true;
+                            let _ = error_sender.send(e);
+                        }
+                    }
+                    Message::Flush(ack) => {
+                        let result = if failed {
+                            Err(broken())
+                        } else {
+                            writer.flush()
+                        };
+                        let _ = ack.send(result);
+                    }
+                }
+            }
+        });
+        Self {
+            sender: Some(sender),
+            worker: Some(worker),
+            errors,
+            failed: false,
+        }
+    }
+
+    fn send(&mut self, message: Message) -> io::Result<()> {
+        if let Ok(e) = self.errors.try_recv() {
+            self.failed = true;
+            return Err(e);
+        }
+        if self.failed {
+            return Err(broken());
+        }
+        self.sender
+            .as_ref()
+            .expect("sender is taken only on drop")
+            .send(message)
+            .map_err(|_| broken())
+    }
+}
+
+impl Write for ThreadedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.send(Message::Data(buf.to_vec()))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let (ack, result) = mpsc::sync_channel(1);
+        self.send(Message::Flush(ack))?;
+        let result = match result.recv() {
+            Ok(Ok(())) => Ok(()),
+            // the error of the failed write is preferred to `broken()`
+            Ok(Err(e)) => Err(self.errors.try_recv().unwrap_or(e)),
+            Err(_) => Err(broken()),
+        };
+        self.failed = result.is_err();
+        result
+    }
+}
+
+impl Drop for ThreadedWriter {
+    fn drop(&mut self) {
+        // closes the channel to stop the background thread
+        self.sender.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn broken() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "writer thread has failed")
+}
@@ -1,5 +1,10 @@
+// Lets code generated by `#[derive(BinaryRead, BinaryWrite)]`, which refers to `::lib`,
+// compile in this crate too.
+extern crate self as lib;
+
 pub mod binary;
 pub mod env;
+pub mod fmt;
 pub mod image;
 pub mod io;
 pub mod path;
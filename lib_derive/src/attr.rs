@@ -0,0 +1,150 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Lit, Meta, NestedMeta, Path, Type};
+
+/// Byte order given by `endian = "..."`.
+#[derive(Clone, Copy)]
+pub(crate) enum Endian {
+    Big,
+    Little,
+    Native,
+}
+
+impl Endian {
+    fn from_lit(lit: &Lit) -> syn::Result<Self> {
+        match lit {
+            Lit::Str(s) if s.value() == "big" => Ok(Endian::Big),
+            Lit::Str(s) if s.value() == "little" => Ok(Endian::Little),
+            Lit::Str(s) if s.value() == "native" => Ok(Endian::Native),
+            _ => Err(syn::Error::new_spanned(
+                lit,
+                r#"endian must be "big", "little" or "native""#,
+            )),
+        }
+    }
+
+    pub(crate) fn tokens(self, krate: &Path) -> TokenStream {
+        match self {
+            Endian::Big => quote!(#krate::binary::Endian::BigEndian),
+            Endian::Little => quote!(#krate::binary::Endian::LittleEndian),
+            Endian::Native => quote!(#krate::binary::Endian::Native),
+        }
+    }
+}
+
+/// `#[binary(...)]` on a struct.
+pub(crate) struct StructAttr {
+    pub(crate) endian: Endian,
+    /// Path to `lib` crate given by `crate = "..."` (default: `::lib`).
+    pub(crate) krate: Path,
+}
+
+impl StructAttr {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut endian = Endian::Big;
+        let mut krate = syn::parse_quote!(::lib);
+        for meta in binary_metas(attrs)? {
+            match &meta {
+                Meta::NameValue(nv) if nv.path.is_ident("endian") => {
+                    endian = Endian::from_lit(&nv.lit)?
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("crate") => match &nv.lit {
+                    Lit::Str(s) => krate = s.parse()?,
+                    lit => {
+                        return Err(syn::Error::new_spanned(
+                            lit,
+                            r#"crate must be a path such as "my_lib""#,
+                        ))
+                    }
+                },
+                _ => return Err(syn::Error::new_spanned(meta, "unknown struct attribute")),
+            }
+        }
+        Ok(Self { endian, krate })
+    }
+}
+
+/// `#[binary(...)]` on a field.
+#[derive(Default)]
+pub(crate) struct FieldAttr {
+    pub(crate) endian: Option<Endian>,
+    /// Type of the element count preceding a `Vec`.
+    pub(crate) len_prefix: Option<Type>,
+    /// Value the field must have.
+    pub(crate) magic: Option<TokenStream>,
+    pub(crate) pad_before: usize,
+    pub(crate) pad_after: usize,
+    /// Whether the field type implements `BinaryRead` / `BinaryWrite` itself.
+    pub(crate) nested: bool,
+}
+
+impl FieldAttr {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut attr = Self::default();
+        for meta in binary_metas(attrs)? {
+            match &meta {
+                Meta::Path(path) if path.is_ident("nested") => attr.nested = true,
+                Meta::NameValue(nv) if nv.path.is_ident("endian") => {
+                    attr.endian = Some(Endian::from_lit(&nv.lit)?)
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("len_prefix") => match &nv.lit {
+                    Lit::Str(s) => attr.len_prefix = Some(s.parse()?),
+                    lit => {
+                        return Err(syn::Error::new_spanned(
+                            lit,
+                            r#"len_prefix must be a type name such as "u16""#,
+                        ))
+                    }
+                },
+                Meta::NameValue(nv) if nv.path.is_ident("magic") => {
+                    attr.magic = Some(match &nv.lit {
+                        Lit::ByteStr(lit) => quote!(*#lit),
+                        lit => quote!(#lit),
+                    })
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("pad_before") => {
+                    attr.pad_before = usize_of(&nv.lit)?
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("pad_after") => {
+                    attr.pad_after = usize_of(&nv.lit)?
+                }
+                _ => return Err(syn::Error::new_spanned(meta, "unknown field attribute")),
+            }
+        }
+        if attr.nested && attr.len_prefix.is_some() {
+            return Err(syn::Error::new_spanned(
+                &attrs[0],
+                "nested and len_prefix cannot be used together",
+            ));
+        }
+        Ok(attr)
+    }
+}
+
+/// Items in all `#[binary(...)]` attributes.
+fn binary_metas(attrs: &[Attribute]) -> syn::Result<Vec<Meta>> {
+    let mut metas = vec![];
+    for attr in attrs.iter().filter(|a| a.path.is_ident("binary")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => {
+                for nested in list.nested {
+                    match nested {
+                        NestedMeta::Meta(meta) => metas.push(meta),
+                        NestedMeta::Lit(lit) => {
+                            return Err(syn::Error::new_spanned(lit, "expected key = value"))
+                        }
+                    }
+                }
+            }
+            meta => return Err(syn::Error::new_spanned(meta, "expected #[binary(...)]")),
+        }
+    }
+    Ok(metas)
+}
+
+fn usize_of(lit: &Lit) -> syn::Result<usize> {
+    match lit {
+        Lit::Int(n) => n.base10_parse(),
+        _ => Err(syn::Error::new_spanned(lit, "expected number of bytes")),
+    }
+}
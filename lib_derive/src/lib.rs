@@ -0,0 +1,383 @@
+//! Derive macros for `lib::binary::BinaryRead` and `lib::binary::BinaryWrite`.
+//!
+//! Fields are read and written in declaration order with `lib::binary::read` / `write`,
+//! so their types must implement `FixedLenBytes` (integers, floats and arrays of them)
+//! unless annotated otherwise.
+//!
+//! Attributes:
+//!
+//! - `#[binary(endian = "little")]` on the struct sets the default byte order of its fields:
+//!   "big" (default), "little" or "native". The same on a field overrides it.
+//! - `#[binary(len_prefix = "u16")]` on a `Vec` field: element count of the given type
+//!   precedes the elements.
+//! - `#[binary(magic = b"BM")]` or `#[binary(magic = 42)]`: reading fails with
+//!   `io::ErrorKind::InvalidData` unless the field has the value.
+//! - `#[binary(pad_before = 2)]` / `#[binary(pad_after = 2)]`: bytes skipped while reading
+//!   and zero-filled while writing.
+//! - `#[binary(nested)]`: the field type implements `BinaryRead` / `BinaryWrite` itself.
+//! - `#[binary(crate = "my_lib")]` on the struct: path to `lib` crate for generated code,
+//!   when the dependency is renamed (default: `::lib`, which also works inside `lib` itself).
+//!
+//! See also `lib::binary::BinaryRead`.
+//!
+//! # Examples
+//!
+//! ```
+//! use lib::binary::{BinaryRead, BinaryWrite};
+//! use std::io;
+//!
+//! #[derive(BinaryRead, BinaryWrite, Debug, PartialEq)]
+//! struct Entry {
+//!     #[binary(pad_before = 1)]
+//!     id: u16,
+//!     #[binary(endian = "native", pad_after = 2)]
+//!     weight: f32,
+//! }
+//!
+//! #[derive(BinaryRead, BinaryWrite, Debug, PartialEq)]
+//! #[binary(endian = "little")]
+//! struct Table {
+//!     #[binary(magic = 0x5442u16)]
+//!     magic: u16,
+//!     #[binary(len_prefix = "u16", endian = "big")]
+//!     ids: Vec<u32>,
+//!     #[binary(nested)]
+//!     entry: Entry,
+//! }
+//!
+//! /// Bytes of a `Table` whose paddings are `pad`.
+//! fn table_bytes(pad: u8) -> Vec<u8> {
+//!     let mut bytes = vec![0x42, 0x54]; // magic in little endian
+//!     bytes.extend_from_slice(&[0, 2, 0, 0, 0, 1, 0, 0, 0, 2]); // ids in big endian
+//!     bytes.extend_from_slice(&[pad, 0x01, 0x02]); // entry.id after padding
+//!     bytes.extend_from_slice(&1.5f32.to_ne_bytes());
+//!     bytes.extend_from_slice(&[pad, pad]);
+//!     bytes
+//! }
+//!
+//! fn main() -> io::Result<()> {
+//!     // paddings are skipped while reading
+//!     let table = Table::read_from(&mut table_bytes(0xEE).as_slice())?;
+//!     assert_eq!(
+//!         table,
+//!         Table {
+//!             magic: 0x5442,
+//!             ids: vec![1, 2],
+//!             entry: Entry { id: 0x0102, weight: 1.5 },
+//!         }
+//!     );
+//!
+//!     // and zero-filled while writing
+//!     let mut written = vec![];
+//!     table.write_to(&mut written)?;
+//!     assert_eq!(written, table_bytes(0));
+//!
+//!     let mut wrong_magic = table_bytes(0);
+//!     wrong_magic[0] = 0x00;
+//!     let err = Table::read_from(&mut wrong_magic.as_slice()).unwrap_err();
+//!     assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+//!     assert_eq!(err.to_string(), "wrong magic number in field `magic`");
+//!
+//!     let truncated = &table_bytes(0)[..8];
+//!     let err = Table::read_from(&mut &truncated[..]).unwrap_err();
+//!     assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+//!
+//!     // element count must fit in the length prefix
+//!     let too_many = Table {
+//!         ids: vec![0; 0x1_0000],
+//!         ..table
+//!     };
+//!     let err = too_many.write_to(&mut vec![]).unwrap_err();
+//!     assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Tuple structs and a renamed dependency:
+//!
+//! ```
+//! extern crate lib as my_lib;
+//!
+//! use my_lib::binary::{BinaryRead, BinaryWrite};
+//!
+//! #[derive(BinaryRead, BinaryWrite, Debug, PartialEq)]
+//! #[binary(crate = "::my_lib")]
+//! struct Version(u8, #[binary(endian = "little")] u16);
+//!
+//! let version = Version::read_from(&mut &[1, 0x02, 0x00][..]).unwrap();
+//! assert_eq!(version, Version(1, 2));
+//! let mut written = vec![];
+//! version.write_to(&mut written).unwrap();
+//! assert_eq!(written, [1, 0x02, 0x00]);
+//! ```
+//!
+//! Wrong attributes are compile errors:
+//!
+//! ```compile_fail
+//! use lib::binary::BinaryRead;
+//!
+//! #[derive(BinaryRead)]
+//! #[binary(endian = "middle")]
+//! struct Wrong(u16);
+//! ```
+//!
+//! ```compile_fail
+//! use lib::binary::BinaryRead;
+//!
+//! #[derive(BinaryRead)]
+//! struct Wrong(#[binary(padding = 2)] u16);
+//! ```
+//!
+//! ```compile_fail
+//! use lib::binary::BinaryRead;
+//!
+//! #[derive(BinaryRead)]
+//! struct Wrong(#[binary(len_prefix = u8)] Vec<u16>);
+//! ```
+//!
+//! ```compile_fail
+//! use lib::binary::BinaryRead;
+//!
+//! #[derive(BinaryRead)]
+//! struct Inner(u8);
+//!
+//! #[derive(BinaryRead)]
+//! struct Wrong(#[binary(nested, len_prefix = "u8")] Vec<Inner>);
+//! ```
+//!
+//! ```compile_fail
+//! use lib::binary::BinaryRead;
+//!
+//! #[derive(BinaryRead)]
+//! enum Wrong {
+//!     A(u8),
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use lib::binary::BinaryRead;
+//!
+//! #[derive(BinaryRead)]
+//! #[binary(crate = "::no_such_crate")]
+//! struct Wrong(u8);
+//! ```
+
+mod attr;
+
+use attr::{FieldAttr, StructAttr};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitStr, Member, Path, Type};
+
+#[proc_macro_derive(BinaryRead, attributes(binary))]
+pub fn derive_binary_read(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_read(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(BinaryWrite, attributes(binary))]
+pub fn derive_binary_write(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_write(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Field of the struct with its attributes.
+struct Field {
+    member: Member,
+    /// Local variable holding the value while reading.
+    var: Ident,
+    ty: Type,
+    /// Field name for error messages.
+    name: LitStr,
+    endian: TokenStream2,
+    attr: FieldAttr,
+}
+
+/// Fields of the struct, their shape and path to `lib` crate.
+fn fields(input: &DeriveInput) -> syn::Result<(Vec<Field>, &Fields, Path)> {
+    let data = match &input.data {
+        Data::Struct(data) => data,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "BinaryRead and BinaryWrite can be derived only for structs",
+            ))
+        }
+    };
+    let struct_attr = StructAttr::from_attrs(&input.attrs)?;
+
+    let fields = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let attr = FieldAttr::from_attrs(&field.attrs)?;
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(i.into()),
+            };
+            let name = match &field.ident {
+                Some(ident) => ident.to_string(),
+                None => i.to_string(),
+            };
+            Ok(Field {
+                member,
+                var: format_ident!("__field{}", i),
+                ty: field.ty.clone(),
+                name: LitStr::new(&name, proc_macro2::Span::call_site()),
+                endian: attr
+                    .endian
+                    .unwrap_or(struct_attr.endian)
+                    .tokens(&struct_attr.krate),
+                attr,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok((fields, &data.fields, struct_attr.krate))
+}
+
+fn expand_read(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let (fields, shape, krate) = fields(input)?;
+
+    let reads = fields.iter().map(|field| {
+        let Field {
+            var,
+            ty,
+            name,
+            endian,
+            attr,
+            ..
+        } = field;
+        let pad_before = skip(attr.pad_before);
+        let pad_after = skip(attr.pad_after);
+        let value = if attr.nested {
+            quote!(#krate::binary::BinaryRead::read_from(reader)?)
+        } else if let Some(prefix) = &attr.len_prefix {
+            quote! {{
+                let len: #prefix = #krate::binary::read(reader, &#endian)?;
+                let mut values = ::std::vec::Vec::new();
+                for _ in 0..len {
+                    values.push(#krate::binary::read(reader, &#endian)?);
+                }
+                values
+            }}
+        } else {
+            quote!(#krate::binary::read(reader, &#endian)?)
+        };
+        let magic = attr.magic.as_ref().map(|magic| {
+            quote! {
+                if #var != #magic {
+                    return ::std::result::Result::Err(::std::io::Error::new(
+                        ::std::io::ErrorKind::InvalidData,
+                        concat!("wrong magic number in field `", #name, "`"),
+                    ));
+                }
+            }
+        });
+        quote! {
+            #pad_before
+            let #var: #ty = #value;
+            #magic
+            #pad_after
+        }
+    });
+
+    let vars = fields.iter().map(|f| &f.var);
+    let construct = match shape {
+        Fields::Named(_) => {
+            let members = fields.iter().map(|f| &f.member);
+            quote!(Self { #(#members: #vars),* })
+        }
+        Fields::Unnamed(_) => quote!(Self(#(#vars),*)),
+        Fields::Unit => quote!(Self),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #krate::binary::BinaryRead for #name #ty_generics #where_clause {
+            fn read_from<__R>(reader: &mut __R) -> ::std::io::Result<Self>
+            where
+                __R: ::std::io::Read,
+            {
+                #(#reads)*
+                ::std::result::Result::Ok(#construct)
+            }
+        }
+    })
+}
+
+fn expand_write(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let (fields, _, krate) = fields(input)?;
+
+    let writes = fields.iter().map(|field| {
+        let Field {
+            member,
+            name,
+            endian,
+            attr,
+            ..
+        } = field;
+        let pad_before = zero_fill(attr.pad_before);
+        let pad_after = zero_fill(attr.pad_after);
+        let value = if attr.nested {
+            quote!(#krate::binary::BinaryWrite::write_to(&self.#member, writer)?;)
+        } else if let Some(prefix) = &attr.len_prefix {
+            quote! {
+                let len = <#prefix as ::std::convert::TryFrom<usize>>::try_from(self.#member.len())
+                    .map_err(|_| ::std::io::Error::new(
+                        ::std::io::ErrorKind::InvalidInput,
+                        concat!("too many elements in field `", #name, "`"),
+                    ))?;
+                #krate::binary::write(writer, len, &#endian)?;
+                #krate::binary::write_from(writer, &self.#member, &#endian)?;
+            }
+        } else {
+            quote! {
+                #krate::binary::write_from(writer, ::std::slice::from_ref(&self.#member), &#endian)?;
+            }
+        };
+        quote! {
+            #pad_before
+            #value
+            #pad_after
+        }
+    });
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #krate::binary::BinaryWrite for #name #ty_generics #where_clause {
+            fn write_to<__W>(&self, writer: &mut __W) -> ::std::io::Result<()>
+            where
+                __W: ::std::io::Write,
+            {
+                #(#writes)*
+                ::std::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+fn skip(len: usize) -> Option<TokenStream2> {
+    if len == 0 {
+        return None;
+    }
+    Some(quote! {{
+        let mut pad = [0u8; #len];
+        ::std::io::Read::read_exact(reader, &mut pad)?;
+    }})
+}
+
+fn zero_fill(len: usize) -> Option<TokenStream2> {
+    if len == 0 {
+        return None;
+    }
+    Some(quote!(::std::io::Write::write_all(writer, &[0u8; #len])?;))
+}